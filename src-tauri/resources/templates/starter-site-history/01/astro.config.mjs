@@ -0,0 +1,9 @@
+import { defineConfig } from 'astro/config';
+
+// https://astro.build/config
+export default defineConfig({
+  output: 'static',
+  outDir: './dist',
+  publicDir: './public',
+  srcDir: './src'
+});
\ No newline at end of file
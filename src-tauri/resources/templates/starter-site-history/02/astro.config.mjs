@@ -0,0 +1,35 @@
+import { defineConfig } from 'astro/config';
+import { existsSync, readFileSync, readdirSync } from 'node:fs';
+import path from 'node:path';
+
+// Redirects recorded by Studio Builder Desktop when posts or projects are renamed.
+// Prefix redirects (renamed project folders) are expanded to one entry per post.
+function loadStudioRedirects() {
+  const file = path.join(process.cwd(), '.studio', 'redirects.json');
+  if (!existsSync(file)) return {};
+
+  const redirects = {};
+  for (const { from, to, prefix } of JSON.parse(readFileSync(file, 'utf-8'))) {
+    redirects[from] = to;
+
+    const targetDir = path.join(process.cwd(), 'src/pages', to);
+    if (!prefix || !to.startsWith('/') || !existsSync(targetDir)) continue;
+
+    for (const entry of readdirSync(targetDir)) {
+      if (entry.endsWith('.md')) {
+        const slug = entry.replace(/\.md$/, '');
+        redirects[`${from}/${slug}`] = `${to}/${slug}`;
+      }
+    }
+  }
+  return redirects;
+}
+
+// https://astro.build/config
+export default defineConfig({
+  output: 'static',
+  outDir: './dist',
+  publicDir: './public',
+  srcDir: './src',
+  redirects: loadStudioRedirects()
+});
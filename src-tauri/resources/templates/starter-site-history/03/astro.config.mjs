@@ -0,0 +1,45 @@
+import { defineConfig } from 'astro/config';
+import { existsSync, readFileSync, readdirSync } from 'node:fs';
+import path from 'node:path';
+
+// Redirects recorded by Studio Builder Desktop when posts or projects are renamed.
+// Prefix redirects (renamed project folders) are expanded to one entry per post.
+function loadStudioRedirects() {
+  const file = path.join(process.cwd(), '.studio', 'redirects.json');
+  if (!existsSync(file)) return {};
+
+  const redirects = {};
+  for (const { from, to, prefix } of JSON.parse(readFileSync(file, 'utf-8'))) {
+    redirects[from] = to;
+
+    const targetDir = path.join(process.cwd(), 'src/pages', to);
+    if (!prefix || !to.startsWith('/') || !existsSync(targetDir)) continue;
+
+    for (const entry of readdirSync(targetDir)) {
+      if (entry.endsWith('.md')) {
+        const slug = entry.replace(/\.md$/, '');
+        redirects[`${from}/${slug}`] = `${to}/${slug}`;
+      }
+    }
+  }
+  return redirects;
+}
+
+// Workspace settings written by Studio Builder Desktop. The app also writes
+// public/sitemap.xml, public/robots.txt and the feeds from the same site URL.
+function loadStudioConfig() {
+  const file = path.join(process.cwd(), 'studio.config.json');
+  return existsSync(file) ? JSON.parse(readFileSync(file, 'utf-8')) : {};
+}
+
+const studioConfig = loadStudioConfig();
+
+// https://astro.build/config
+export default defineConfig({
+  site: studioConfig.url || undefined,
+  output: 'static',
+  outDir: './dist',
+  publicDir: './public',
+  srcDir: './src',
+  redirects: loadStudioRedirects()
+});
@@ -0,0 +1,43 @@
+import { defineConfig } from 'astro/config';
+import mdx from '@astrojs/mdx';
+import { existsSync, readFileSync, readdirSync } from 'node:fs';
+import path from 'node:path';
+import { loadSiteConfig } from './src/lib/site-config.js';
+
+// Redirects recorded by Studio Builder Desktop when posts or projects are renamed.
+// Prefix redirects (renamed project folders) are expanded to one entry per post.
+function loadStudioRedirects() {
+  const file = path.join(process.cwd(), '.studio', 'redirects.json');
+  if (!existsSync(file)) return {};
+
+  const redirects = {};
+  for (const { from, to, prefix } of JSON.parse(readFileSync(file, 'utf-8'))) {
+    redirects[from] = to;
+
+    const targetDir = path.join(process.cwd(), 'src/pages', to);
+    if (!prefix || !to.startsWith('/') || !existsSync(targetDir)) continue;
+
+    for (const entry of readdirSync(targetDir)) {
+      if (/\.mdx?$/.test(entry)) {
+        const slug = entry.replace(/\.mdx?$/, '');
+        redirects[`${from}/${slug}`] = `${to}/${slug}`;
+      }
+    }
+  }
+  return redirects;
+}
+
+// Workspace settings written by Studio Builder Desktop. The app also writes
+// public/sitemap.xml, public/robots.txt and the feeds from the same site URL.
+const siteConfig = loadSiteConfig();
+
+// https://astro.build/config
+export default defineConfig({
+  site: siteConfig.url || undefined,
+  output: 'static',
+  outDir: './dist',
+  publicDir: './public',
+  srcDir: './src',
+  integrations: [mdx()],
+  redirects: loadStudioRedirects()
+});
@@ -0,0 +1,29 @@
+import { existsSync, readFileSync } from 'node:fs';
+import path from 'node:path';
+
+// Fallbacks for settings studio.config.json leaves unset
+const DEFAULT_COLORS = {
+  primary: '#171717',
+  background: '#ffffff',
+  text: '#171717',
+  muted: '#737373',
+};
+
+// Workspace settings edited in Studio Builder Desktop's site settings
+export function loadSiteConfig() {
+  const file = path.join(process.cwd(), 'studio.config.json');
+  const config = existsSync(file) ? JSON.parse(readFileSync(file, 'utf-8')) : {};
+  return {
+    ...config,
+    title: config.title || 'Studio',
+    language: config.language || 'en',
+    colors: { ...DEFAULT_COLORS, ...config.colors },
+    nav: config.nav ?? [],
+  };
+}
+
+// The theme colors as CSS custom properties for a style attribute
+export const colorVariables = (colors) =>
+  Object.entries(colors)
+    .map(([name, value]) => `--color-${name}: ${value};`)
+    .join(' ');
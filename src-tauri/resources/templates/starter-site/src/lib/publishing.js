@@ -0,0 +1,67 @@
+import { existsSync, readdirSync, readFileSync, rmSync, writeFileSync } from 'node:fs';
+import path from 'node:path';
+import { fileURLToPath } from 'node:url';
+import { isPublished } from './taxonomy.js';
+
+// Markdown and MDX pages below src/pages with the route they build to
+function listPosts(dir, route = '') {
+  if (!existsSync(dir)) return [];
+
+  const posts = [];
+  for (const entry of readdirSync(dir, { withFileTypes: true })) {
+    if (/^[._]/.test(entry.name)) continue;
+    if (entry.isDirectory()) {
+      posts.push(...listPosts(path.join(dir, entry.name), `${route}/${entry.name}`));
+    } else if (/\.mdx?$/.test(entry.name)) {
+      const slug = entry.name.replace(/\.mdx?$/, '');
+      posts.push({ file: path.join(dir, entry.name), route: `${route}/${slug}` });
+    }
+  }
+  return posts;
+}
+
+// Marks scheduled posts whose publishAt has passed as published, as the app does
+function publishDuePosts(posts) {
+  const published = [];
+  for (const post of posts) {
+    const source = readFileSync(post.file, 'utf-8');
+    const block = source.match(/^---\r?\n[\s\S]*?\r?\n---/)?.[0];
+    if (!block || !/^status:\s*["']?scheduled["']?\s*$/m.test(block) || !isPublished(source)) continue;
+
+    const updated = block.replace(/^status:.*$/m, 'status: published');
+    writeFileSync(post.file, updated + source.slice(block.length));
+    published.push(post.route);
+  }
+  return published;
+}
+
+// Publishes due scheduled posts before the build and removes the pages of
+// drafts, posts in review and posts scheduled for later from its output
+export default function studioPublishing() {
+  const pagesDir = path.join(process.cwd(), 'src/pages');
+  let hidden = [];
+
+  return {
+    name: 'studio-publishing',
+    hooks: {
+      'astro:build:start': ({ logger }) => {
+        const posts = listPosts(pagesDir);
+        for (const route of publishDuePosts(posts)) {
+          logger.info(`Published scheduled post ${route}`);
+        }
+        hidden = posts.filter(post => !isPublished(readFileSync(post.file, 'utf-8')));
+      },
+      'astro:build:done': ({ dir, logger }) => {
+        const outDir = fileURLToPath(dir);
+        for (const { route } of hidden) {
+          // build.format 'directory' writes <route>/index.html, 'file' writes <route>.html
+          const pageDir = path.join(outDir, route);
+          rmSync(path.join(pageDir, 'index.html'), { force: true });
+          if (existsSync(pageDir) && readdirSync(pageDir).length === 0) rmSync(pageDir, { recursive: true });
+          rmSync(`${pageDir}.html`, { force: true });
+          logger.info(`Left unpublished post ${route} out of the build`);
+        }
+      },
+    },
+  };
+}
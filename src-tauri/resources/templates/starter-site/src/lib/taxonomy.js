@@ -0,0 +1,111 @@
+import { existsSync } from 'node:fs';
+import { readdir, readFile } from 'node:fs/promises';
+import path from 'node:path';
+
+// The tag pages folder, told apart from a project named `tags` by its dynamic route
+export const isTagPagesDir = (pagesDir, name) =>
+  name === 'tags' && existsSync(path.join(pagesDir, name, '[tag].astro'));
+
+const frontmatterBlock = (source) =>
+  source.match(/^---\r?\n([\s\S]*?)\r?\n---/)?.[1] ?? '';
+
+const unquote = (value) => {
+  const v = value.trim();
+  if (/^".*"$/.test(v)) return v.slice(1, -1).replace(/\\(["\\])/g, '$1');
+  if (/^'.*'$/.test(v)) return v.slice(1, -1).replace(/''/g, "'");
+  return v;
+};
+
+// Read a top-level field from a post's frontmatter
+const frontmatterField = (source, key) => {
+  const line = frontmatterBlock(source).split(/\r?\n/).find(l => l.startsWith(`${key}:`));
+  return line === undefined ? undefined : unquote(line.slice(key.length + 1));
+};
+
+// Read a list field written inline (`tags: ["a", "b"]`) or as `- item` lines
+const frontmatterList = (source, key) => {
+  const lines = frontmatterBlock(source).split(/\r?\n/);
+  const index = lines.findIndex(l => l.startsWith(`${key}:`));
+  if (index === -1) return [];
+
+  const value = lines[index].slice(key.length + 1).trim();
+  if (value === '') {
+    const items = [];
+    for (const line of lines.slice(index + 1)) {
+      if (!/^\s/.test(line)) break;
+      const item = line.trim().replace(/^-\s*/, '');
+      if (item) items.push(unquote(item));
+    }
+    return items;
+  }
+  if (!value.startsWith('[')) return [unquote(value)];
+
+  const inner = value.slice(1, -1);
+  return (inner.match(/"(?:[^"\\]|\\.)*"|'[^']*'|[^,]+/g) ?? [])
+    .map(item => unquote(item.trim()))
+    .filter(Boolean);
+};
+
+// Matches the publishing rules of the project index pages
+export const isPublished = (source) => {
+  const status = frontmatterField(source, 'status') ?? 'published';
+  if (status === 'published') return true;
+  if (status !== 'scheduled') return false;
+  const publishAt = new Date(frontmatterField(source, 'publishAt'));
+  return !Number.isNaN(publishAt.getTime()) && publishAt <= new Date();
+};
+
+export const tagSlug = (tag) =>
+  tag.toLowerCase().replace(/[^\p{L}\p{N}]+/gu, '-').replace(/^-|-$/g, '');
+
+// Published posts in a project folder and its sections, which are nested folders
+async function loadFolder(pagesDir, folder, posts) {
+  const entries = await readdir(path.join(pagesDir, folder), { withFileTypes: true });
+  for (const entry of entries) {
+    if (entry.isDirectory() && !/^[._]/.test(entry.name)) {
+      await loadFolder(pagesDir, `${folder}/${entry.name}`, posts);
+    } else if (entry.isFile() && /\.mdx?$/.test(entry.name)) {
+      const source = await readFile(path.join(pagesDir, folder, entry.name), 'utf-8');
+      if (!isPublished(source)) continue;
+
+      const slug = entry.name.replace(/\.mdx?$/, '');
+      posts.push({
+        title: frontmatterField(source, 'title') ?? slug,
+        href: `/${folder}/${slug}`,
+        tags: frontmatterList(source, 'tags'),
+      });
+    }
+  }
+}
+
+// Every published post in the workspace with its tags
+export async function loadTaggedPosts() {
+  const pagesDir = path.join(process.cwd(), 'src/pages');
+  const posts = [];
+
+  const projects = (await readdir(pagesDir, { withFileTypes: true }))
+    .filter(entry => entry.isDirectory() && !/^[._]/.test(entry.name))
+    .map(entry => entry.name)
+    .filter(name => !isTagPagesDir(pagesDir, name));
+
+  for (const project of projects) {
+    await loadFolder(pagesDir, project, posts);
+  }
+  return posts;
+}
+
+// Tags with their published posts, most used first
+export async function loadTags() {
+  const tags = new Map();
+  for (const post of await loadTaggedPosts()) {
+    for (const name of post.tags) {
+      const slug = tagSlug(name);
+      if (!slug) continue;
+      if (!tags.has(slug)) tags.set(slug, { name, slug, posts: [] });
+      tags.get(slug).posts.push(post);
+    }
+  }
+  return [...tags.values()].sort(
+    (a, b) => b.posts.length - a.posts.length || a.name.localeCompare(b.name)
+  );
+}
@@ -0,0 +1,100 @@
+use crate::project_manifest;
+use crate::sections;
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(Debug, Serialize)]
+pub struct AdoptResult {
+    project: crate::Project,
+    created: Vec<String>, // Paths below the project folder, e.g. `guides/index.astro`
+    kept: Vec<String>,    // Scaffold files skipped for pages the folder already had
+}
+
+/// Whether the folder has the pages studio generates for every project.
+/// Folders made by hand or checked out from git usually don't. An adopted
+/// folder may serve its own index page in place of `index.astro`.
+pub(crate) fn is_managed(project_path: &Path) -> bool {
+    project_path.join("_layout.astro").is_file() && sections::has_index_page(project_path)
+}
+
+/// Adds the studio scaffolding and manifest to a folder in src/pages, and to
+/// each folder inside it that holds posts as a section. Only missing files are
+/// written; posts and pages the folder already has are left alone, and a
+/// folder with an index page of its own keeps it.
+#[tauri::command]
+pub async fn adopt_project(
+    folder_name: String,
+    display_name: Option<String>,
+) -> Result<AdoptResult, String> {
+    let studio_path = crate::studio_dir()?;
+    let project_path = sections::resolve_project_path(&studio_path, &folder_name)?;
+    if folder_name.contains('/') {
+        return Err(format!(
+            "'{}' is a section; adopt its project instead",
+            folder_name
+        ));
+    }
+
+    let pages_path = studio_path.join("src").join("pages");
+    let paths = sections::section_paths(&studio_path, &folder_name)?;
+    let adopted = paths.iter().all(|path| is_managed(&pages_path.join(path)))
+        && project_manifest::manifest_path(&project_path).exists();
+    if adopted {
+        return Err(format!("Project '{}' is already managed", folder_name));
+    }
+
+    let display_name = display_name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .or_else(|| crate::read_project_display_name(&project_path))
+        .unwrap_or_else(|| folder_name.clone());
+
+    let mut created = Vec::new();
+    let mut kept = Vec::new();
+
+    for path in paths {
+        let dir = pages_path.join(&path);
+        let name = if path == folder_name {
+            display_name.clone()
+        } else {
+            let folder = path.rsplit('/').next().unwrap_or(&path);
+            crate::read_project_display_name(&dir).unwrap_or_else(|| folder.to_string())
+        };
+        let relative = |file: &str| match path.strip_prefix(&format!("{}/", folder_name)) {
+            Some(section) => format!("{}/{}", section, file),
+            None => file.to_string(),
+        };
+
+        for (file, content) in crate::render_project_scaffold(&dir, &name, &path) {
+            if dir.join(file).exists() || (file == "index.astro" && sections::has_index_page(&dir))
+            {
+                kept.push(relative(file));
+            } else {
+                crate::write_file_atomic(&dir.join(file), &content)?;
+                created.push(relative(file));
+            }
+        }
+
+        if !project_manifest::manifest_path(&dir).exists() {
+            let manifest = project_manifest::ProjectManifest {
+                name: Some(name),
+                ..Default::default()
+            };
+            project_manifest::save_manifest(&dir, &manifest)?;
+            created.push(relative(project_manifest::MANIFEST_FILE));
+        }
+    }
+
+    crate::refresh_site_files(&studio_path)?;
+
+    Ok(AdoptResult {
+        project: crate::Project {
+            name: display_name,
+            folder_name,
+            path: project_path.to_string_lossy().to_string(),
+            managed: true,
+        },
+        created,
+        kept,
+    })
+}
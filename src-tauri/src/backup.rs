@@ -0,0 +1,384 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+const MANIFEST_NAME: &str = "studio-backup.json";
+const BACKUP_FORMAT: &str = "studio-backup";
+const BACKUP_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupManifest {
+    format: String,
+    version: u32,
+    app_version: String,
+    created_at: String,
+    files: Vec<BackupFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupFile {
+    path: String, // Relative to the workspace, always with `/` separators
+    size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupSummary {
+    path: String,
+    file_count: usize,
+    total_bytes: u64,
+}
+
+/// Collects every workspace file worth backing up. Installed packages and
+/// build output can be regenerated, so `node_modules` and the top-level
+/// `dist` folder are skipped.
+fn collect_backup_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<(PathBuf, BackupFile)>,
+) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let file_type = entry
+            .file_type()
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let name = entry.file_name();
+
+        if file_type.is_symlink() {
+            continue;
+        }
+
+        if file_type.is_dir() {
+            if name == "node_modules" || (dir == root && name == "dist") {
+                continue;
+            }
+            collect_backup_files(root, &path, files)?;
+        } else if file_type.is_file() {
+            let relative = path
+                .strip_prefix(root)
+                .map_err(|_| format!("{} is outside the workspace", path.display()))?;
+            let relative = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+
+            files.push((
+                path,
+                BackupFile {
+                    path: relative,
+                    size,
+                },
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn write_backup(studio_path: &Path, archive_path: &Path) -> Result<BackupSummary, String> {
+    let mut files = Vec::new();
+    collect_backup_files(studio_path, studio_path, &mut files)?;
+    files.sort_by(|a, b| a.1.path.cmp(&b.1.path));
+
+    // Build next to the destination, then rename, so a failed backup never
+    // leaves a truncated archive under the final name
+    let temp_path = archive_path.with_extension("zip.tmp");
+    let written = write_archive(&temp_path, files).and_then(|counts| {
+        fs::rename(&temp_path, archive_path)
+            .map_err(|e| format!("Failed to move backup into place: {}", e))?;
+        Ok(counts)
+    });
+    if written.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    let (file_count, total_bytes) = written?;
+    Ok(BackupSummary {
+        path: archive_path.to_string_lossy().to_string(),
+        file_count,
+        total_bytes,
+    })
+}
+
+/// Writes the files and their manifest to `temp_path`, returning the file count and size.
+fn write_archive(
+    temp_path: &Path,
+    files: Vec<(PathBuf, BackupFile)>,
+) -> Result<(usize, u64), String> {
+    let file =
+        fs::File::create(temp_path).map_err(|e| format!("Failed to create backup file: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let mut total_bytes = 0;
+    for (source, entry) in &files {
+        zip.start_file(entry.path.as_str(), options)
+            .map_err(|e| format!("Failed to add {} to backup: {}", entry.path, e))?;
+        let content =
+            fs::read(source).map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+        zip.write_all(&content)
+            .map_err(|e| format!("Failed to write {} to backup: {}", entry.path, e))?;
+        total_bytes += entry.size;
+    }
+
+    let file_count = files.len();
+    let manifest = BackupManifest {
+        format: BACKUP_FORMAT.to_string(),
+        version: BACKUP_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        files: files.into_iter().map(|(_, entry)| entry).collect(),
+    };
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize backup manifest: {}", e))?;
+
+    zip.start_file(MANIFEST_NAME, options)
+        .map_err(|e| format!("Failed to add manifest to backup: {}", e))?;
+    zip.write_all(manifest_json.as_bytes())
+        .map_err(|e| format!("Failed to write manifest to backup: {}", e))?;
+    zip.finish()
+        .map_err(|e| format!("Failed to finish backup: {}", e))?;
+
+    Ok((file_count, total_bytes))
+}
+
+fn read_manifest(archive: &mut zip::ZipArchive<fs::File>) -> Result<BackupManifest, String> {
+    let mut manifest_file = archive
+        .by_name(MANIFEST_NAME)
+        .map_err(|_| "This archive is not a studio backup (no manifest found)".to_string())?;
+    let mut manifest_json = String::new();
+    manifest_file
+        .read_to_string(&mut manifest_json)
+        .map_err(|e| format!("Failed to read backup manifest: {}", e))?;
+
+    let manifest: BackupManifest = serde_json::from_str(&manifest_json)
+        .map_err(|e| format!("Backup manifest is invalid: {}", e))?;
+
+    if manifest.format != BACKUP_FORMAT {
+        return Err(format!("Unknown backup format '{}'", manifest.format));
+    }
+    if manifest.version > BACKUP_VERSION {
+        return Err(format!(
+            "Backup was made by a newer version of the app (format version {})",
+            manifest.version
+        ));
+    }
+
+    Ok(manifest)
+}
+
+#[tauri::command]
+pub async fn export_workspace_backup(dest: String) -> Result<BackupSummary, String> {
+    let studio_path = crate::studio_dir()?;
+    if !studio_path.exists() {
+        return Err("Studio workspace does not exist".to_string());
+    }
+
+    let dest_path = PathBuf::from(&dest);
+    let archive_path = if dest_path.is_dir() {
+        dest_path.join(format!(
+            "studio-backup-{}.zip",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ))
+    } else {
+        dest_path
+    };
+
+    let studio_canonical = studio_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve workspace path: {}", e))?;
+    let dest_parent = archive_path
+        .parent()
+        .and_then(|parent| parent.canonicalize().ok())
+        .ok_or("Backup destination folder does not exist")?;
+    if dest_parent.starts_with(&studio_canonical) {
+        return Err("Save the backup outside the studio folder".to_string());
+    }
+
+    write_backup(&studio_path, &archive_path)
+}
+
+#[tauri::command]
+pub async fn restore_workspace_backup(
+    archive: String,
+    target: String,
+    force: Option<bool>,
+) -> Result<BackupSummary, String> {
+    restore_backup(
+        Path::new(&archive),
+        Path::new(&target),
+        force.unwrap_or(false),
+    )
+}
+
+fn restore_backup(
+    archive: &Path,
+    target_path: &Path,
+    force: bool,
+) -> Result<BackupSummary, String> {
+    let file = fs::File::open(archive).map_err(|e| format!("Failed to open backup: {}", e))?;
+    let mut zip =
+        zip::ZipArchive::new(file).map_err(|e| format!("Failed to read backup archive: {}", e))?;
+    let manifest = read_manifest(&mut zip)?;
+
+    // Validate everything before writing a single file
+    for entry in &manifest.files {
+        let archived = zip
+            .by_name(&entry.path)
+            .map_err(|_| format!("Backup is missing {}", entry.path))?;
+        if archived.enclosed_name().is_none() {
+            return Err(format!("Backup contains an unsafe path: {}", entry.path));
+        }
+        if archived.size() != entry.size {
+            return Err(format!("Backup entry {} is corrupted", entry.path));
+        }
+    }
+
+    let is_empty = match fs::read_dir(target_path) {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(_) => true,
+    };
+    if !is_empty && !force {
+        return Err(format!(
+            "{} is not empty; restore into an empty folder or force it",
+            target_path.display()
+        ));
+    }
+
+    fs::create_dir_all(target_path)
+        .map_err(|e| format!("Failed to create restore folder: {}", e))?;
+
+    let mut total_bytes = 0;
+    for entry in &manifest.files {
+        let mut archived = zip
+            .by_name(&entry.path)
+            .map_err(|e| format!("Failed to read {} from backup: {}", entry.path, e))?;
+        let relative = archived
+            .enclosed_name()
+            .ok_or_else(|| format!("Backup contains an unsafe path: {}", entry.path))?;
+        let out_path = target_path.join(relative);
+
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+
+        let mut content = Vec::with_capacity(entry.size as usize);
+        archived
+            .read_to_end(&mut content)
+            .map_err(|e| format!("Failed to extract {}: {}", entry.path, e))?;
+        fs::write(&out_path, &content)
+            .map_err(|e| format!("Failed to write {}: {}", out_path.display(), e))?;
+        total_bytes += entry.size;
+    }
+
+    Ok(BackupSummary {
+        path: target_path.to_string_lossy().to_string(),
+        file_count: manifest.files.len(),
+        total_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("studio-backup-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn restores_what_it_backed_up() {
+        let root = temp_dir("round-trip");
+        let studio_path = root.join("studio");
+        fs::create_dir_all(studio_path.join("src/pages/blog")).unwrap();
+        fs::create_dir_all(studio_path.join("node_modules/astro")).unwrap();
+        fs::create_dir_all(studio_path.join("dist")).unwrap();
+        fs::write(studio_path.join("src/pages/blog/hello.md"), "# Hello").unwrap();
+        fs::write(studio_path.join("package.json"), "{}").unwrap();
+        fs::write(studio_path.join("node_modules/astro/index.js"), "").unwrap();
+        fs::write(studio_path.join("dist/index.html"), "").unwrap();
+
+        let archive_path = root.join("backup.zip");
+        let summary = write_backup(&studio_path, &archive_path).unwrap();
+        assert_eq!(summary.file_count, 2);
+        assert_eq!(summary.total_bytes, 9);
+        assert!(!archive_path.with_extension("zip.tmp").exists());
+
+        let target_path = root.join("restored");
+        let restored = restore_backup(&archive_path, &target_path, false).unwrap();
+        assert_eq!(restored.file_count, 2);
+        assert_eq!(
+            fs::read_to_string(target_path.join("src/pages/blog/hello.md")).unwrap(),
+            "# Hello"
+        );
+        assert!(!target_path.join("node_modules").exists());
+        assert!(!target_path.join("dist").exists());
+
+        // A second restore into the same folder needs force
+        assert!(restore_backup(&archive_path, &target_path, false).is_err());
+        assert!(restore_backup(&archive_path, &target_path, true).is_ok());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn removes_the_temp_file_when_a_backup_fails() {
+        let root = temp_dir("failed");
+        let studio_path = root.join("studio");
+        fs::create_dir_all(&studio_path).unwrap();
+        fs::write(studio_path.join("package.json"), "{}").unwrap();
+
+        // The archive can't replace a folder, so the final rename fails
+        let archive_path = root.join("backup.zip");
+        fs::create_dir_all(archive_path.join("taken")).unwrap();
+
+        assert!(write_backup(&studio_path, &archive_path).is_err());
+        assert!(!archive_path.with_extension("zip.tmp").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn rejects_entries_outside_the_target() {
+        let root = temp_dir("unsafe");
+        let archive_path = root.join("backup.zip");
+
+        let manifest = BackupManifest {
+            format: BACKUP_FORMAT.to_string(),
+            version: BACKUP_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            files: vec![BackupFile {
+                path: "../escaped.txt".to_string(),
+                size: 4,
+            }],
+        };
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive_path).unwrap());
+        let options = SimpleFileOptions::default();
+        zip.start_file("../escaped.txt", options).unwrap();
+        zip.write_all(b"oops").unwrap();
+        zip.start_file(MANIFEST_NAME, options).unwrap();
+        zip.write_all(serde_json::to_string(&manifest).unwrap().as_bytes())
+            .unwrap();
+        zip.finish().unwrap();
+
+        let target_path = root.join("restored");
+        let error = restore_backup(&archive_path, &target_path, false).unwrap_err();
+        assert!(error.contains("unsafe path"));
+        assert!(!root.join("escaped.txt").exists());
+        assert!(!target_path.exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,403 @@
+use crate::project_manifest;
+use crate::sections;
+use crate::site_posts;
+use serde::Serialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+// The layouts every generated _layout.astro and index.astro import
+const LAYOUT_FILES: [&str; 3] = [
+    "src/layouts/Layout.astro",
+    "src/layouts/PostLayout.astro",
+    "src/layouts/ProjectLayout.astro",
+];
+
+// Astro 5 needs Node 18.17.1 or later
+const MIN_NODE_MAJOR: u32 = 18;
+
+// Folders that are generated or installed rather than written by the app
+const SKIPPED_DIRS: [&str; 4] = ["node_modules", ".git", "dist", ".astro"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DoctorCheck {
+    id: String,
+    title: String,
+    status: CheckStatus,
+    message: String,
+    fix: Option<String>, // What to do about a warning or failure
+}
+
+impl DoctorCheck {
+    fn pass(id: &str, title: &str, message: impl Into<String>) -> Self {
+        DoctorCheck {
+            id: id.to_string(),
+            title: title.to_string(),
+            status: CheckStatus::Pass,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    fn problem(
+        status: CheckStatus,
+        id: &str,
+        title: &str,
+        message: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        DoctorCheck {
+            id: id.to_string(),
+            title: title.to_string(),
+            status,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// The trimmed output of `<program> --version`, if the program runs.
+fn tool_version(program: &str) -> Option<String> {
+    // npm, pnpm and yarn are batch scripts on Windows
+    let program = if cfg!(windows) && program != "node" && program != "bun" {
+        format!("{}.cmd", program)
+    } else {
+        program.to_string()
+    };
+
+    let output = Command::new(program).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Some(version).filter(|version| !version.is_empty())
+}
+
+/// The package manager the workspace's lockfile belongs to; npm without one.
+fn package_manager(studio_path: &Path) -> &'static str {
+    [
+        ("bun.lock", "bun"),
+        ("bun.lockb", "bun"),
+        ("pnpm-lock.yaml", "pnpm"),
+        ("yarn.lock", "yarn"),
+        ("package-lock.json", "npm"),
+    ]
+    .into_iter()
+    .find(|(lockfile, _)| studio_path.join(lockfile).exists())
+    .map(|(_, manager)| manager)
+    .unwrap_or("npm")
+}
+
+fn check_package_json(studio_path: &Path) -> DoctorCheck {
+    let (id, title) = ("package_json", "package.json");
+    match fs::read_to_string(studio_path.join("package.json")) {
+        Err(_) => DoctorCheck::problem(
+            CheckStatus::Fail,
+            id,
+            title,
+            "package.json is missing, so the site can't be installed or built",
+            "Upgrade the starter template to restore it",
+        ),
+        Ok(content) => match serde_json::from_str::<serde_json::Value>(&content) {
+            Err(e) => DoctorCheck::problem(
+                CheckStatus::Fail,
+                id,
+                title,
+                format!("package.json is not valid JSON: {}", e),
+                "Fix the syntax error at the reported line",
+            ),
+            Ok(json) if json["dependencies"]["astro"].is_null() => DoctorCheck::problem(
+                CheckStatus::Warn,
+                id,
+                title,
+                "package.json does not list astro as a dependency",
+                "Add \"astro\" to the dependencies in package.json",
+            ),
+            Ok(_) => DoctorCheck::pass(id, title, "package.json is present and valid"),
+        },
+    }
+}
+
+fn check_layouts(studio_path: &Path) -> DoctorCheck {
+    let (id, title) = ("layouts", "Layout files");
+    let missing: Vec<&str> = LAYOUT_FILES
+        .into_iter()
+        .filter(|layout| !studio_path.join(layout).is_file())
+        .collect();
+
+    if missing.is_empty() {
+        DoctorCheck::pass(
+            id,
+            title,
+            "All layouts the project pages import are present",
+        )
+    } else {
+        DoctorCheck::problem(
+            CheckStatus::Fail,
+            id,
+            title,
+            format!("Missing {}", missing.join(", ")),
+            "Apply a theme to restore the layouts",
+        )
+    }
+}
+
+fn check_node() -> DoctorCheck {
+    let (id, title) = ("node", "Node.js");
+    let Some(version) = tool_version("node") else {
+        return DoctorCheck::problem(
+            CheckStatus::Fail,
+            id,
+            title,
+            "node was not found on PATH",
+            "Install Node.js 18.17 or later from nodejs.org, then restart the app",
+        );
+    };
+
+    let major = version
+        .trim_start_matches('v')
+        .split('.')
+        .next()
+        .and_then(|major| major.parse::<u32>().ok());
+    match major {
+        Some(major) if major < MIN_NODE_MAJOR => DoctorCheck::problem(
+            CheckStatus::Warn,
+            id,
+            title,
+            format!("node {} is older than Astro supports", version),
+            "Install Node.js 18.17 or later",
+        ),
+        _ => DoctorCheck::pass(id, title, format!("node {}", version)),
+    }
+}
+
+fn check_package_manager(manager: &str) -> DoctorCheck {
+    let (id, title) = ("package_manager", "Package manager");
+    match tool_version(manager) {
+        Some(version) => DoctorCheck::pass(id, title, format!("{} {}", manager, version)),
+        None => DoctorCheck::problem(
+            CheckStatus::Fail,
+            id,
+            title,
+            format!(
+                "The workspace uses {}, which was not found on PATH",
+                manager
+            ),
+            format!("Install {}, then restart the app", manager),
+        ),
+    }
+}
+
+fn check_node_modules(studio_path: &Path, manager: &str) -> DoctorCheck {
+    let (id, title) = ("node_modules", "Installed dependencies");
+    let fix = format!("Run `{} install` in {}", manager, studio_path.display());
+
+    if !studio_path.join("node_modules").is_dir() {
+        DoctorCheck::problem(
+            CheckStatus::Fail,
+            id,
+            title,
+            "Dependencies are not installed",
+            fix,
+        )
+    } else if !studio_path
+        .join("node_modules")
+        .join("astro")
+        .join("package.json")
+        .is_file()
+    {
+        DoctorCheck::problem(
+            CheckStatus::Fail,
+            id,
+            title,
+            "node_modules exists but astro is not installed",
+            fix,
+        )
+    } else {
+        DoctorCheck::pass(id, title, "node_modules is installed")
+    }
+}
+
+fn find_temp_files(dir: &Path, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            if !SKIPPED_DIRS.contains(&name.as_str()) {
+                find_temp_files(&path, found);
+            }
+        } else if name.starts_with('.') && name.ends_with(".tmp") {
+            found.push(path);
+        }
+    }
+}
+
+fn check_temp_files(studio_path: &Path) -> DoctorCheck {
+    let (id, title) = ("temp_files", "Interrupted saves");
+    let mut found = Vec::new();
+    find_temp_files(studio_path, &mut found);
+
+    if found.is_empty() {
+        return DoctorCheck::pass(id, title, "No leftover temporary files");
+    }
+
+    let paths: Vec<String> = found
+        .iter()
+        .map(|path| {
+            path.strip_prefix(studio_path)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect();
+    DoctorCheck::problem(
+        CheckStatus::Warn,
+        id,
+        title,
+        format!(
+            "Found temporary files left by interrupted saves: {}",
+            paths.join(", ")
+        ),
+        "Each holds a save that never finished; copy anything newer into the file next to it, then delete them",
+    )
+}
+
+/// Resolves `..` and `.` in a relative import without touching the disk.
+fn resolve_import(from: &Path, target: &str) -> PathBuf {
+    let mut resolved = from.to_path_buf();
+    for component in Path::new(target).components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::Normal(part) => resolved.push(part),
+            _ => {}
+        }
+    }
+    resolved
+}
+
+/// Checks a project or section has its generated pages, and that their
+/// relative imports resolve from that folder. Imports of missing layouts
+/// are left to the layouts check. Folders the app never managed, without a
+/// `_project.json`, are pointed at adopting `project`, the top-level folder.
+fn check_project(studio_path: &Path, project: &str, path: &str) -> DoctorCheck {
+    let id = format!("project:{}", path);
+    let title = format!("Project {}", path);
+    let project_path = studio_path.join("src").join("pages").join(path);
+    let fix = format!(
+        "Repair {} to regenerate its index.astro and _layout.astro",
+        path
+    );
+
+    if !project_manifest::manifest_path(&project_path).exists() {
+        return DoctorCheck::problem(
+            CheckStatus::Warn,
+            &id,
+            &title,
+            "Not managed by the app yet, so its pages may be missing or out of date",
+            format!("Adopt {} to add its pages and _project.json", project),
+        );
+    }
+
+    // An adopted folder may keep its own index page instead of index.astro
+    let mut missing = Vec::new();
+    if !sections::has_index_page(&project_path) {
+        missing.push("index.astro");
+    }
+    if !project_path.join("_layout.astro").is_file() {
+        missing.push("_layout.astro");
+    }
+    if !missing.is_empty() {
+        return DoctorCheck::problem(
+            CheckStatus::Fail,
+            &id,
+            &title,
+            format!("Missing {}", missing.join(" and ")),
+            fix,
+        );
+    }
+
+    let mut unresolved = Vec::new();
+    for page in ["index.astro", "_layout.astro"] {
+        let Ok(content) = fs::read_to_string(project_path.join(page)) else {
+            continue;
+        };
+        for import in content
+            .lines()
+            .filter(|line| line.trim_start().starts_with("import "))
+            .filter_map(|line| line.split(['\'', '"']).nth(1))
+            .filter(|target| target.starts_with('.'))
+        {
+            let resolved = resolve_import(&project_path, import);
+            let layouts_path = studio_path.join("src").join("layouts");
+            if !resolved.exists() && resolved.parent() != Some(layouts_path.as_path()) {
+                unresolved.push(format!("{} imports {}", page, import));
+            }
+        }
+    }
+
+    if unresolved.is_empty() {
+        DoctorCheck::pass(&id, &title, "Its index page and _layout.astro are in place")
+    } else {
+        DoctorCheck::problem(
+            CheckStatus::Fail,
+            &id,
+            &title,
+            format!("Broken imports: {}", unresolved.join("; ")),
+            fix,
+        )
+    }
+}
+
+/// Runs every workspace check. Failures are reported as results; the command
+/// itself only errors when the checks can't run at all.
+#[tauri::command]
+pub async fn doctor() -> Result<Vec<DoctorCheck>, String> {
+    let studio_path = crate::studio_dir()?;
+
+    if !studio_path.is_dir() {
+        return Ok(vec![DoctorCheck::problem(
+            CheckStatus::Fail,
+            "workspace",
+            "Workspace",
+            format!("{} does not exist", studio_path.display()),
+            "Restart the app to create a new workspace",
+        )]);
+    }
+
+    let manager = package_manager(&studio_path);
+    let mut checks = vec![
+        DoctorCheck::pass(
+            "workspace",
+            "Workspace",
+            format!("Found {}", studio_path.display()),
+        ),
+        check_package_json(&studio_path),
+        check_layouts(&studio_path),
+        check_node(),
+        check_package_manager(manager),
+        check_node_modules(&studio_path, manager),
+        check_temp_files(&studio_path),
+    ];
+
+    // Only sections: folders of images and other files have no pages to check
+    for project in site_posts::project_folders(&studio_path)? {
+        for path in sections::section_paths(&studio_path, &project)? {
+            checks.push(check_project(&studio_path, &project, &path));
+        }
+    }
+
+    Ok(checks)
+}
@@ -0,0 +1,606 @@
+use crate::frontmatter;
+use crate::post_format::{self, PostFormat};
+use crate::post_status::{self, PostStatus};
+use crate::project_manifest;
+use crate::sitemap::encode_path;
+use pulldown_cmark::{html, Event, Options, Parser, Tag};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::CompressionMethod;
+
+const STYLESHEET: &str = "body { font-family: serif; line-height: 1.5; }
+h1, h2, h3 { font-family: sans-serif; line-height: 1.2; }
+img { max-width: 100%; }
+pre { white-space: pre-wrap; }
+";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EpubSummary {
+    path: String,
+    chapters: usize,
+    images: usize,
+}
+
+struct Chapter {
+    slug: String,
+    title: String,
+    body: String, // XHTML fragment
+}
+
+struct EpubImage {
+    href: String, // Path inside OEBPS
+    media_type: &'static str,
+    data: Vec<u8>,
+}
+
+fn image_media_type(path: &str) -> Option<&'static str> {
+    let extension = path.rsplit('.').next()?.to_lowercase();
+    match extension.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "svg" => Some("image/svg+xml"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Collects the images a book needs, loading each site path from `public/` once.
+struct ImageStore<'a> {
+    public_path: &'a Path,
+    images: Vec<EpubImage>,
+    sources: Vec<String>,
+}
+
+impl ImageStore<'_> {
+    /// Returns the image's path inside OEBPS, or `None` if it is not a local site image.
+    fn add(&mut self, site_path: &str) -> Option<String> {
+        if let Some(index) = self.sources.iter().position(|s| s == site_path) {
+            return Some(self.images[index].href.clone());
+        }
+
+        let relative = site_path.strip_prefix('/')?;
+        let media_type = image_media_type(relative)?;
+        let data = fs::read(self.public_path.join(relative)).ok()?;
+        let name = relative.rsplit('/').next().unwrap_or(relative);
+        let href = format!("images/{}-{}", self.images.len() + 1, name);
+
+        self.images.push(EpubImage {
+            href: href.clone(),
+            media_type,
+            data,
+        });
+        self.sources.push(site_path.to_string());
+        Some(href)
+    }
+}
+
+/// Renders markdown to an XHTML fragment. Local images are embedded and links
+/// to other chapters of the book point at their XHTML files.
+fn render_chapter(
+    markdown: &str,
+    project_name: &str,
+    chapter_slugs: &[String],
+    images: &mut ImageStore,
+) -> String {
+    let project_prefix = format!("/{}/", project_name);
+    let parser = Parser::new_ext(markdown, Options::all()).map(|event| match event {
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => {
+            let dest_url = match images.add(&dest_url) {
+                Some(href) => format!("../{}", encode_path(&href)).into(),
+                None => dest_url,
+            };
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            })
+        }
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => {
+            let chapter = dest_url
+                .strip_prefix(&project_prefix)
+                .map(|rest| rest.split(['#', '?']).next().unwrap_or(rest))
+                .and_then(|slug| {
+                    chapter_slugs
+                        .iter()
+                        .find(|s| *s == slug || encode_path(s) == slug)
+                });
+            let dest_url = match chapter {
+                Some(slug) => format!("{}.xhtml", encode_path(slug)).into(),
+                None => dest_url,
+            };
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            })
+        }
+        // Raw HTML may not be well-formed XML, so show it as text instead
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        event => event,
+    });
+
+    let mut output = String::new();
+    html::push_html(&mut output, parser);
+    output
+}
+
+fn xhtml_document(title: &str, language: &str, stylesheet: &str, body: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops" xml:lang="{lang}" lang="{lang}">
+<head>
+  <meta charset="UTF-8" />
+  <title>{title}</title>
+  <link rel="stylesheet" type="text/css" href="{stylesheet}" />
+</head>
+<body>
+{body}
+</body>
+</html>
+"#,
+        lang = crate::escape_xml(language),
+        title = crate::escape_xml(title),
+        stylesheet = stylesheet,
+        body = body
+    )
+}
+
+fn nav_document(title: &str, language: &str, chapters: &[Chapter]) -> String {
+    let items: String = chapters
+        .iter()
+        .map(|chapter| {
+            format!(
+                "      <li><a href=\"text/{}.xhtml\">{}</a></li>\n",
+                crate::escape_xml(&encode_path(&chapter.slug)),
+                crate::escape_xml(&chapter.title)
+            )
+        })
+        .collect();
+
+    xhtml_document(
+        title,
+        language,
+        "style.css",
+        &format!(
+            "  <nav epub:type=\"toc\" id=\"toc\">\n    <h1>Contents</h1>\n    <ol>\n{}    </ol>\n  </nav>",
+            items
+        ),
+    )
+}
+
+fn package_document(
+    identifier: &str,
+    title: &str,
+    language: &str,
+    author: Option<&str>,
+    chapters: &[Chapter],
+    images: &[EpubImage],
+    cover: Option<&str>,
+) -> String {
+    let mut metadata = format!(
+        "    <dc:identifier id=\"book-id\">{}</dc:identifier>\n    <dc:title>{}</dc:title>\n    <dc:language>{}</dc:language>\n    <meta property=\"dcterms:modified\">{}</meta>\n",
+        crate::escape_xml(identifier),
+        crate::escape_xml(title),
+        crate::escape_xml(language),
+        chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ")
+    );
+    if let Some(author) = author {
+        metadata.push_str(&format!(
+            "    <dc:creator>{}</dc:creator>\n",
+            crate::escape_xml(author)
+        ));
+    }
+
+    let mut manifest = String::from(
+        "    <item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>\n    <item id=\"style\" href=\"style.css\" media-type=\"text/css\"/>\n",
+    );
+    let mut spine = String::new();
+
+    if cover.is_some() {
+        manifest.push_str(
+            "    <item id=\"cover\" href=\"text/cover.xhtml\" media-type=\"application/xhtml+xml\"/>\n",
+        );
+        spine.push_str("    <itemref idref=\"cover\" linear=\"no\"/>\n");
+    }
+    for (index, chapter) in chapters.iter().enumerate() {
+        manifest.push_str(&format!(
+            "    <item id=\"chapter-{}\" href=\"text/{}.xhtml\" media-type=\"application/xhtml+xml\"/>\n",
+            index + 1,
+            crate::escape_xml(&encode_path(&chapter.slug))
+        ));
+        spine.push_str(&format!("    <itemref idref=\"chapter-{}\"/>\n", index + 1));
+    }
+    for (index, image) in images.iter().enumerate() {
+        let properties = if Some(image.href.as_str()) == cover {
+            " properties=\"cover-image\""
+        } else {
+            ""
+        };
+        manifest.push_str(&format!(
+            "    <item id=\"image-{}\" href=\"{}\" media-type=\"{}\"{}/>\n",
+            index + 1,
+            crate::escape_xml(&encode_path(&image.href)),
+            image.media_type,
+            properties
+        ));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="book-id" xml:lang="{}">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+{}  </metadata>
+  <manifest>
+{}  </manifest>
+  <spine>
+{}  </spine>
+</package>
+"#,
+        crate::escape_xml(language),
+        metadata,
+        manifest,
+        spine
+    )
+}
+
+fn write_epub(archive_path: &Path, files: Vec<(String, Vec<u8>)>) -> Result<(), String> {
+    let temp_path = archive_path.with_extension("epub.tmp");
+    let file =
+        fs::File::create(&temp_path).map_err(|e| format!("Failed to create EPUB file: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+
+    // The mimetype entry must come first and be stored uncompressed
+    zip.start_file(
+        "mimetype",
+        SimpleFileOptions::default().compression_method(CompressionMethod::Stored),
+    )
+    .map_err(|e| format!("Failed to write EPUB: {}", e))?;
+    zip.write_all(b"application/epub+zip")
+        .map_err(|e| format!("Failed to write EPUB: {}", e))?;
+
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, data) in files {
+        zip.start_file(name.as_str(), options)
+            .map_err(|e| format!("Failed to add {} to EPUB: {}", name, e))?;
+        zip.write_all(&data)
+            .map_err(|e| format!("Failed to add {} to EPUB: {}", name, e))?;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to finish EPUB: {}", e))?;
+
+    fs::rename(&temp_path, archive_path)
+        .map_err(|e| format!("Failed to move EPUB into place: {}", e))
+}
+
+fn build_epub(studio_path: &Path, project_name: &str, dest: &str) -> Result<EpubSummary, String> {
+    let project_path = crate::sections::resolve_project_path(studio_path, project_name)?;
+
+    let manifest = project_manifest::load_manifest(&project_path)?;
+    let title = crate::read_project_display_name(&project_path).unwrap_or(project_name.to_string());
+    let language = manifest
+        .language
+        .clone()
+        .unwrap_or_else(|| "en".to_string());
+
+    // Published posts only, in the order the project lists them
+    let mut sources = Vec::new();
+    for slug in crate::ordered_post_slugs(&project_path)? {
+        let (path, format) = post_format::find_post_file(&project_path, &slug)
+            .ok_or_else(|| format!("Post '{}' does not exist", slug))?;
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read post '{}': {}", slug, e))?;
+        if post_status::read_post_state(&content).status != PostStatus::Published {
+            continue;
+        }
+        // Components can't be rendered into the book, so their imports go
+        let content = match format {
+            PostFormat::Mdx => post_format::strip_mdx_statements(&content),
+            PostFormat::Md => content,
+        };
+        sources.push((slug, content));
+    }
+
+    if sources.is_empty() {
+        return Err("Project has no published posts to export".to_string());
+    }
+
+    let chapter_slugs: Vec<String> = sources.iter().map(|(slug, _)| slug.clone()).collect();
+    let mut images = ImageStore {
+        public_path: &studio_path.join("public"),
+        images: Vec::new(),
+        sources: Vec::new(),
+    };
+
+    let cover = match &manifest.cover {
+        Some(cover) => Some(
+            images
+                .add(cover)
+                .ok_or_else(|| format!("Cover image {} was not found in public/", cover))?,
+        ),
+        None => None,
+    };
+
+    let mut chapters = Vec::new();
+    for (slug, content) in &sources {
+        let chapter_title = crate::extract_title_from_markdown(content, slug);
+        let body = frontmatter::split(content)
+            .map(|(_, body)| body)
+            .unwrap_or(content);
+
+        let mut xhtml = String::new();
+        // Posts created in the app already open with their title as a heading
+        if !body.trim_start().starts_with("# ") {
+            xhtml.push_str(&format!("<h1>{}</h1>\n", crate::escape_xml(&chapter_title)));
+        }
+        xhtml.push_str(&render_chapter(
+            body,
+            project_name,
+            &chapter_slugs,
+            &mut images,
+        ));
+
+        chapters.push(Chapter {
+            slug: slug.clone(),
+            title: chapter_title,
+            body: xhtml,
+        });
+    }
+
+    let identifier = format!(
+        "urn:studio:{}:{}",
+        project_name,
+        chrono::Utc::now().format("%Y%m%d%H%M%S")
+    );
+
+    let mut files: Vec<(String, Vec<u8>)> = vec![
+        (
+            "META-INF/container.xml".to_string(),
+            br#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#
+            .to_vec(),
+        ),
+        (
+            "OEBPS/content.opf".to_string(),
+            package_document(
+                &identifier,
+                &title,
+                &language,
+                manifest.author.as_deref(),
+                &chapters,
+                &images.images,
+                cover.as_deref(),
+            )
+            .into_bytes(),
+        ),
+        (
+            "OEBPS/nav.xhtml".to_string(),
+            nav_document(&title, &language, &chapters).into_bytes(),
+        ),
+        (
+            "OEBPS/style.css".to_string(),
+            STYLESHEET.as_bytes().to_vec(),
+        ),
+    ];
+
+    if let Some(cover) = &cover {
+        let body = format!(
+            "<section epub:type=\"cover\"><img src=\"../{}\" alt=\"{}\" /></section>",
+            crate::escape_xml(&encode_path(cover)),
+            crate::escape_xml(&title)
+        );
+        files.push((
+            "OEBPS/text/cover.xhtml".to_string(),
+            xhtml_document(&title, &language, "../style.css", &body).into_bytes(),
+        ));
+    }
+    for chapter in &chapters {
+        let body = format!(
+            "<section epub:type=\"chapter\">\n{}</section>",
+            chapter.body
+        );
+        files.push((
+            format!("OEBPS/text/{}.xhtml", chapter.slug),
+            xhtml_document(&chapter.title, &language, "../style.css", &body).into_bytes(),
+        ));
+    }
+
+    let image_count = images.images.len();
+    for image in images.images {
+        files.push((format!("OEBPS/{}", image.href), image.data));
+    }
+
+    let dest_path = PathBuf::from(dest);
+    let archive_path = if dest_path.is_dir() {
+        // Sections are exported under their own folder name
+        let file_stem = project_name.rsplit('/').next().unwrap_or(project_name);
+        dest_path.join(format!("{}.epub", file_stem))
+    } else {
+        dest_path
+    };
+    write_epub(&archive_path, files)?;
+
+    Ok(EpubSummary {
+        path: archive_path.to_string_lossy().to_string(),
+        chapters: chapters.len(),
+        images: image_count,
+    })
+}
+
+#[tauri::command]
+pub async fn export_project_epub(
+    project_name: String,
+    dest: String,
+) -> Result<EpubSummary, String> {
+    build_epub(&crate::studio_dir()?, &project_name, &dest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use zip::ZipArchive;
+
+    // A 1x1 transparent PNG
+    const PNG: [u8; 67] = [
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1F,
+        0x15, 0xC4, 0x89, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0x00,
+        0x01, 0x00, 0x00, 0x05, 0x00, 0x01, 0x0D, 0x0A, 0x2D, 0xB4, 0x00, 0x00, 0x00, 0x00, 0x49,
+        0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+    ];
+
+    /// A project with a cover, two published chapters sharing an image, and a draft.
+    fn fixture_studio(name: &str) -> PathBuf {
+        let studio_path =
+            std::env::temp_dir().join(format!("studio-epub-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&studio_path);
+        let project_path = studio_path.join("src/pages/book");
+        let media_path = studio_path.join("public/media/book");
+        fs::create_dir_all(&project_path).unwrap();
+        fs::create_dir_all(&media_path).unwrap();
+        fs::write(media_path.join("cover.png"), PNG).unwrap();
+        fs::write(media_path.join("figure.png"), PNG).unwrap();
+
+        let manifest = project_manifest::ProjectManifest {
+            name: Some("The Book".to_string()),
+            author: Some("A. Writer".to_string()),
+            cover: Some("/media/book/cover.png".to_string()),
+            post_order: vec!["intro".to_string(), "next".to_string()],
+            ..Default::default()
+        };
+        project_manifest::save_manifest(&project_path, &manifest).unwrap();
+
+        fs::write(
+            project_path.join("intro.md"),
+            "---\ntitle: \"Intro\"\n---\n# Intro\n\n![Figure](/media/book/figure.png)\n\nOn to [the next chapter](/book/next).\n",
+        )
+        .unwrap();
+        fs::write(
+            project_path.join("next.md"),
+            "---\ntitle: \"Next\"\n---\n# Next\n\n![Again](/media/book/figure.png)\n",
+        )
+        .unwrap();
+        fs::write(
+            project_path.join("later.md"),
+            "---\ntitle: \"Later\"\nstatus: draft\n---\n# Later\n",
+        )
+        .unwrap();
+        studio_path
+    }
+
+    fn read_entry(archive: &mut ZipArchive<fs::File>, name: &str) -> String {
+        let mut content = String::new();
+        archive
+            .by_name(name)
+            .unwrap_or_else(|_| panic!("{} is missing", name))
+            .read_to_string(&mut content)
+            .unwrap();
+        content
+    }
+
+    #[test]
+    fn builds_a_valid_epub_from_a_project() {
+        let studio_path = fixture_studio("project");
+        let dest = studio_path.join("book.epub");
+        let summary = build_epub(&studio_path, "book", dest.to_str().unwrap()).unwrap();
+        assert_eq!(summary.chapters, 2);
+        assert_eq!(summary.images, 2);
+
+        let mut archive = ZipArchive::new(fs::File::open(&dest).unwrap()).unwrap();
+        {
+            let mimetype = archive.by_index(0).unwrap();
+            assert_eq!(mimetype.name(), "mimetype");
+            assert_eq!(mimetype.compression(), CompressionMethod::Stored);
+        }
+        assert_eq!(read_entry(&mut archive, "mimetype"), "application/epub+zip");
+        assert!(read_entry(&mut archive, "META-INF/container.xml").contains("OEBPS/content.opf"));
+
+        let nav = read_entry(&mut archive, "OEBPS/nav.xhtml");
+        assert!(nav.contains("epub:type=\"toc\""));
+        assert!(nav.contains("text/intro.xhtml") && nav.contains("text/next.xhtml"));
+        assert!(!nav.contains("later"));
+
+        let package = read_entry(&mut archive, "OEBPS/content.opf");
+        assert!(package.contains(
+            "href=\"images/1-cover.png\" media-type=\"image/png\" properties=\"cover-image\""
+        ));
+        assert!(package.contains("href=\"images/2-figure.png\" media-type=\"image/png\"/>"));
+        assert!(package.contains("href=\"text/cover.xhtml\""));
+        assert!(package.contains("<dc:creator>A. Writer</dc:creator>"));
+        for href in ["images/1-cover.png", "images/2-figure.png"] {
+            assert!(archive.by_name(&format!("OEBPS/{}", href)).is_ok());
+        }
+
+        // Links between chapters point at their XHTML files
+        let intro = read_entry(&mut archive, "OEBPS/text/intro.xhtml");
+        assert!(intro.contains("href=\"next.xhtml\""));
+        assert!(intro.contains("src=\"../images/2-figure.png\""));
+
+        // Run epubcheck too when it is installed
+        if let Ok(output) = std::process::Command::new("epubcheck").arg(&dest).output() {
+            assert!(
+                output.status.success(),
+                "epubcheck failed:\n{}",
+                String::from_utf8_lossy(&output.stdout)
+            );
+        }
+
+        fs::remove_dir_all(studio_path).unwrap();
+    }
+
+    #[test]
+    fn exports_a_section_with_encoded_hrefs() {
+        let studio_path = fixture_studio("section");
+        let section_path = studio_path.join("src/pages/book/part-2");
+        fs::create_dir_all(&section_path).unwrap();
+        fs::write(studio_path.join("public/media/book/my figure.png"), PNG).unwrap();
+        fs::write(
+            section_path.join("café.md"),
+            "---\ntitle: \"Café\"\n---\n# Café\n\n![Figure](</media/book/my figure.png>)\n\nSee [again](/book/part-2/caf%C3%A9).\n",
+        )
+        .unwrap();
+
+        let dest = studio_path.join("exports");
+        fs::create_dir_all(&dest).unwrap();
+        let summary = build_epub(&studio_path, "book/part-2", dest.to_str().unwrap()).unwrap();
+        assert_eq!(summary.path, dest.join("part-2.epub").to_string_lossy());
+
+        let mut archive =
+            ZipArchive::new(fs::File::open(dest.join("part-2.epub")).unwrap()).unwrap();
+        let nav = read_entry(&mut archive, "OEBPS/nav.xhtml");
+        assert!(nav.contains("href=\"text/caf%C3%A9.xhtml\""));
+        let package = read_entry(&mut archive, "OEBPS/content.opf");
+        assert!(package.contains("href=\"text/caf%C3%A9.xhtml\""));
+        assert!(package.contains("href=\"images/1-my%20figure.png\""));
+
+        // Entries keep their names; only the references are encoded
+        let chapter = read_entry(&mut archive, "OEBPS/text/café.xhtml");
+        assert!(chapter.contains("src=\"../images/1-my%20figure.png\""));
+        assert!(chapter.contains("href=\"caf%C3%A9.xhtml\""));
+        assert!(archive.by_name("OEBPS/images/1-my figure.png").is_ok());
+
+        fs::remove_dir_all(studio_path).unwrap();
+    }
+}
@@ -0,0 +1,394 @@
+use crate::project_manifest::{self, FeedContent};
+use crate::site_config;
+use crate::site_posts::{self, SitePost};
+use chrono::{DateTime, Utc};
+use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+const RSS_FILE: &str = "rss.xml";
+const ATOM_FILE: &str = "atom.xml";
+const JSON_FEED_FILE: &str = "feed.json";
+const FEED_FILES: [&str; 3] = [RSS_FILE, ATOM_FILE, JSON_FEED_FILE];
+const GENERATOR: &str = "Studio Builder Desktop";
+
+// Feeds list the most recent posts only
+const FEED_LIMIT: usize = 50;
+const SUMMARY_LENGTH: usize = 280;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedSummary {
+    files: Vec<String>, // Paths relative to the workspace
+    entries: usize,
+}
+
+struct FeedEntry {
+    url: String,
+    title: String,
+    summary: String,
+    content_html: Option<String>, // Only when the project publishes full content
+    published: DateTime<Utc>,
+    updated: DateTime<Utc>,
+    tags: Vec<String>,
+}
+
+struct Feed {
+    title: String,
+    author: String,
+    home_url: String,
+    feed_url: String, // Folder the feed files are served from, without a trailing slash
+    entries: Vec<FeedEntry>,
+}
+
+impl Feed {
+    fn updated(&self) -> DateTime<Utc> {
+        self.entries
+            .iter()
+            .map(|entry| entry.updated)
+            .max()
+            .unwrap_or_else(Utc::now)
+    }
+}
+
+/// Renders markdown to HTML with site-relative links and images made absolute,
+/// since feed readers show entries away from the site.
+fn render_html(markdown: &str, base_url: &str) -> String {
+    let absolute = |url: pulldown_cmark::CowStr<'static>| -> pulldown_cmark::CowStr<'static> {
+        if url.starts_with('/') && !url.starts_with("//") {
+            format!("{}{}", base_url, url).into()
+        } else {
+            url
+        }
+    };
+
+    let parser = Parser::new_ext(markdown, Options::all()).map(|event| match event {
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: absolute(dest_url.into_static()),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: absolute(dest_url.into_static()),
+            title,
+            id,
+        }),
+        event => event,
+    });
+
+    let mut output = String::new();
+    html::push_html(&mut output, parser);
+    output
+}
+
+/// The post's description, or the text of its first paragraph cut to a readable length.
+fn summarize(post: &SitePost) -> String {
+    if let Some(description) = &post.description {
+        return description.clone();
+    }
+
+    let mut text = String::new();
+    let mut in_paragraph = false;
+    for event in Parser::new(&post.body) {
+        match event {
+            Event::Start(Tag::Paragraph) => in_paragraph = true,
+            Event::End(TagEnd::Paragraph) if !text.trim().is_empty() => break,
+            Event::Text(value) | Event::Code(value) if in_paragraph => text.push_str(&value),
+            Event::SoftBreak | Event::HardBreak if in_paragraph => text.push(' '),
+            _ => {}
+        }
+    }
+
+    let text = text.trim();
+    if text.chars().count() <= SUMMARY_LENGTH {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(SUMMARY_LENGTH).collect();
+    let cut = cut.rsplit_once(' ').map(|(head, _)| head).unwrap_or(&cut);
+    format!("{}…", cut.trim_end_matches([',', '.', ';', ':']))
+}
+
+fn feed_entry(post: &SitePost, base_url: &str, content: FeedContent) -> FeedEntry {
+    FeedEntry {
+        url: format!("{}{}", base_url, post.url_path()),
+        title: post.title.clone(),
+        summary: summarize(post),
+        content_html: (content == FeedContent::Full).then(|| render_html(&post.body, base_url)),
+        published: post.date.unwrap_or(post.updated),
+        updated: post.updated,
+        tags: post.tags.clone(),
+    }
+}
+
+fn rss_document(feed: &Feed) -> String {
+    let mut items = String::new();
+    for entry in &feed.entries {
+        items.push_str(&format!(
+            "    <item>\n      <title>{}</title>\n      <link>{}</link>\n      <guid isPermaLink=\"true\">{}</guid>\n      <pubDate>{}</pubDate>\n      <description>{}</description>\n",
+            crate::escape_xml(&entry.title),
+            crate::escape_xml(&entry.url),
+            crate::escape_xml(&entry.url),
+            entry.published.to_rfc2822(),
+            crate::escape_xml(&entry.summary)
+        ));
+        if let Some(content) = &entry.content_html {
+            items.push_str(&format!(
+                "      <content:encoded>{}</content:encoded>\n",
+                crate::escape_xml(content)
+            ));
+        }
+        for tag in &entry.tags {
+            items.push_str(&format!(
+                "      <category>{}</category>\n",
+                crate::escape_xml(tag)
+            ));
+        }
+        items.push_str("    </item>\n");
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+  <channel>
+    <title>{title}</title>
+    <link>{home}</link>
+    <description>{title}</description>
+    <atom:link href="{feed}/{file}" rel="self" type="application/rss+xml"/>
+    <lastBuildDate>{updated}</lastBuildDate>
+    <generator>{generator}</generator>
+{items}  </channel>
+</rss>
+"#,
+        title = crate::escape_xml(&feed.title),
+        home = crate::escape_xml(&feed.home_url),
+        feed = crate::escape_xml(&feed.feed_url),
+        file = RSS_FILE,
+        updated = feed.updated().to_rfc2822(),
+        generator = GENERATOR,
+        items = items
+    )
+}
+
+fn atom_document(feed: &Feed) -> String {
+    let mut entries = String::new();
+    for entry in &feed.entries {
+        entries.push_str(&format!(
+            "  <entry>\n    <title>{}</title>\n    <id>{}</id>\n    <link href=\"{}\"/>\n    <published>{}</published>\n    <updated>{}</updated>\n    <summary>{}</summary>\n",
+            crate::escape_xml(&entry.title),
+            crate::escape_xml(&entry.url),
+            crate::escape_xml(&entry.url),
+            entry.published.to_rfc3339(),
+            entry.updated.to_rfc3339(),
+            crate::escape_xml(&entry.summary)
+        ));
+        if let Some(content) = &entry.content_html {
+            entries.push_str(&format!(
+                "    <content type=\"html\">{}</content>\n",
+                crate::escape_xml(content)
+            ));
+        }
+        for tag in &entry.tags {
+            entries.push_str(&format!(
+                "    <category term=\"{}\"/>\n",
+                crate::escape_xml(tag)
+            ));
+        }
+        entries.push_str("  </entry>\n");
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>{title}</title>
+  <id>{home}</id>
+  <link href="{home}"/>
+  <link rel="self" type="application/atom+xml" href="{feed}/{file}"/>
+  <updated>{updated}</updated>
+  <generator>{generator}</generator>
+  <author>
+    <name>{author}</name>
+  </author>
+{entries}</feed>
+"#,
+        title = crate::escape_xml(&feed.title),
+        home = crate::escape_xml(&feed.home_url),
+        feed = crate::escape_xml(&feed.feed_url),
+        file = ATOM_FILE,
+        updated = feed.updated().to_rfc3339(),
+        author = crate::escape_xml(&feed.author),
+        generator = GENERATOR,
+        entries = entries
+    )
+}
+
+fn json_feed_document(feed: &Feed) -> Result<String, String> {
+    let items: Vec<serde_json::Value> = feed
+        .entries
+        .iter()
+        .map(|entry| {
+            let mut item = serde_json::json!({
+                "id": entry.url,
+                "url": entry.url,
+                "title": entry.title,
+                "summary": entry.summary,
+                "date_published": entry.published.to_rfc3339(),
+                "date_modified": entry.updated.to_rfc3339(),
+                "tags": entry.tags,
+            });
+            // JSON Feed requires one of the two content fields
+            match &entry.content_html {
+                Some(content) => item["content_html"] = content.clone().into(),
+                None => item["content_text"] = entry.summary.clone().into(),
+            }
+            item
+        })
+        .collect();
+
+    let document = serde_json::json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": feed.title,
+        "home_page_url": feed.home_url,
+        "feed_url": format!("{}/{}", feed.feed_url, JSON_FEED_FILE),
+        "authors": [{ "name": feed.author }],
+        "items": items,
+    });
+
+    serde_json::to_string_pretty(&document)
+        .map_err(|e| format!("Failed to serialize JSON feed: {}", e))
+}
+
+/// Writes the three feed files into `dir` and returns their workspace-relative paths.
+fn write_feed(studio_path: &Path, dir: &Path, feed: &Feed) -> Result<Vec<String>, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create feed folder: {}", e))?;
+
+    let documents = [
+        (RSS_FILE, rss_document(feed)),
+        (ATOM_FILE, atom_document(feed)),
+        (JSON_FEED_FILE, json_feed_document(feed)?),
+    ];
+
+    let mut written = Vec::new();
+    for (file, content) in documents {
+        let path = dir.join(file);
+        crate::write_file_atomic(&path, &content)?;
+        written.push(
+            path.strip_prefix(studio_path)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/"),
+        );
+    }
+    Ok(written)
+}
+
+fn sort_and_limit(entries: &mut Vec<FeedEntry>) {
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.published));
+    entries.truncate(FEED_LIMIT);
+}
+
+/// Removes feed files left in `public/` by projects that were renamed or deleted.
+fn remove_stale_feeds(public_path: &Path, projects: &[String]) {
+    let Ok(entries) = fs::read_dir(public_path) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !dir.is_dir() || name == "media" || projects.contains(&name) {
+            continue;
+        }
+
+        // Leave alone any feed the user put there by hand
+        let generated = fs::read_to_string(dir.join(RSS_FILE))
+            .is_ok_and(|rss| rss.contains(&format!("<generator>{}</generator>", GENERATOR)));
+        if !generated {
+            continue;
+        }
+
+        for file in FEED_FILES {
+            let _ = fs::remove_file(dir.join(file));
+        }
+        // Only succeeds when nothing else lives in the folder
+        let _ = fs::remove_dir(&dir);
+    }
+}
+
+/// Regenerates the workspace feeds in `public/` and one set per project in
+/// `public/<project>/`, so they are served at `/rss.xml` and `/<project>/rss.xml`.
+pub(crate) fn generate_all_feeds(studio_path: &Path) -> Result<FeedSummary, String> {
+    let config = site_config::load_site_config(studio_path)?;
+    let base_url = config
+        .base_url()
+        .ok_or("Set the site URL in studio.config.json before generating feeds")?
+        .to_string();
+    let site_title = config.title.clone().unwrap_or_else(|| "Studio".to_string());
+
+    let public_path = studio_path.join("public");
+    let projects = site_posts::project_folders(studio_path)?;
+    let mut files = Vec::new();
+    let mut workspace_entries = Vec::new();
+
+    for project in &projects {
+        let project_path = studio_path.join("src").join("pages").join(project);
+        let manifest = project_manifest::load_manifest(&project_path)?;
+        let content = manifest.feed_content.unwrap_or_default();
+
+        let mut entries: Vec<FeedEntry> =
+            site_posts::published_posts_with_sections(studio_path, project)?
+                .iter()
+                .map(|post| feed_entry(post, &base_url, content))
+                .collect();
+        sort_and_limit(&mut entries);
+
+        let title = crate::read_project_display_name(&project_path).unwrap_or(project.clone());
+        let feed = Feed {
+            author: manifest
+                .author
+                .clone()
+                .unwrap_or_else(|| site_title.clone()),
+            title,
+            home_url: format!("{}/{}", base_url, project),
+            feed_url: format!("{}/{}", base_url, project),
+            entries,
+        };
+        files.extend(write_feed(studio_path, &public_path.join(project), &feed)?);
+
+        workspace_entries.extend(feed.entries);
+    }
+
+    sort_and_limit(&mut workspace_entries);
+    let entry_count = workspace_entries.len();
+    let feed = Feed {
+        title: site_title.clone(),
+        author: site_title,
+        home_url: format!("{}/", base_url),
+        feed_url: base_url.clone(),
+        entries: workspace_entries,
+    };
+    files.extend(write_feed(studio_path, &public_path, &feed)?);
+
+    remove_stale_feeds(&public_path, &projects);
+
+    Ok(FeedSummary {
+        files,
+        entries: entry_count,
+    })
+}
+
+#[tauri::command]
+pub async fn generate_feeds() -> Result<FeedSummary, String> {
+    generate_all_feeds(&crate::studio_dir()?)
+}
@@ -0,0 +1,201 @@
+// Minimal line-based frontmatter handling, matching the `key: value` style
+// that create_post and create_project write.
+
+/// Splits `content` into its frontmatter lines (without the `---` fences) and the body.
+pub(crate) fn split(content: &str) -> Option<(Vec<&str>, &str)> {
+    let mut lines = content.split_inclusive('\n');
+    let first = lines.next()?;
+    if first.trim() != "---" {
+        return None;
+    }
+
+    let mut offset = first.len();
+    let mut fields = Vec::new();
+    for line in lines {
+        offset += line.len();
+        if line.trim() == "---" {
+            return Some((fields, &content[offset..]));
+        }
+        fields.push(line.trim_end_matches(['\n', '\r']));
+    }
+
+    None
+}
+
+/// Returns the unquoted value of a top-level `key:` field.
+pub(crate) fn get_field(content: &str, key: &str) -> Option<String> {
+    let (fields, _) = split(content)?;
+    fields
+        .iter()
+        .find_map(|line| field_value(line, key))
+        .map(|value| unquote(value.trim()))
+}
+
+/// Returns the items of a list field, written either inline (`["a", "b"]`) or as
+/// an indented block of `- item` lines. A plain value counts as a single item.
+pub(crate) fn get_list(content: &str, key: &str) -> Vec<String> {
+    let Some((fields, _)) = split(content) else {
+        return Vec::new();
+    };
+    let Some(index) = fields
+        .iter()
+        .position(|line| field_value(line, key).is_some())
+    else {
+        return Vec::new();
+    };
+
+    let value = field_value(fields[index], key).unwrap_or_default().trim();
+    if value.is_empty() {
+        return fields[index + 1..]
+            .iter()
+            .take_while(|line| line.starts_with(' ') || line.starts_with('\t'))
+            .filter_map(|line| line.trim().strip_prefix('-'))
+            .map(|item| unquote(item.trim()))
+            .filter(|item| !item.is_empty())
+            .collect();
+    }
+
+    let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) else {
+        return vec![unquote(value)];
+    };
+
+    // Split on commas outside quotes
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quote_char: Option<char> = None;
+    let mut escaped = false;
+    for c in inner.chars() {
+        match quote_char {
+            Some(q) => {
+                if c == q && !escaped {
+                    quote_char = None;
+                }
+                escaped = c == '\\' && !escaped;
+                current.push(c);
+            }
+            None if c == '"' || c == '\'' => {
+                quote_char = Some(c);
+                current.push(c);
+            }
+            None if c == ',' => items.push(std::mem::take(&mut current)),
+            None => current.push(c),
+        }
+    }
+    items.push(current);
+
+    items
+        .iter()
+        .map(|item| unquote(item.trim()))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Sets `key` to the already formatted `value`, adding the field (or the whole
+/// frontmatter block) when it is missing.
+pub(crate) fn set_field(content: &str, key: &str, value: &str) -> String {
+    let new_line = format!("{}: {}", key, value);
+
+    let Some((fields, body)) = split(content) else {
+        return format!("---\n{}\n---\n\n{}", new_line, content);
+    };
+
+    let mut lines: Vec<String> = Vec::with_capacity(fields.len() + 1);
+    let mut replaced = false;
+    let mut skipping_block = false;
+
+    for line in fields {
+        if skipping_block {
+            // Drop the indented items of a block list that belonged to the old value
+            if line.starts_with(' ') || line.starts_with('\t') {
+                continue;
+            }
+            skipping_block = false;
+        }
+
+        if !replaced && field_value(line, key).is_some() {
+            lines.push(new_line.clone());
+            replaced = true;
+            skipping_block = true;
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+
+    if !replaced {
+        lines.push(new_line);
+    }
+
+    format!("---\n{}\n---\n{}", lines.join("\n"), body)
+}
+
+/// Removes a top-level field (and any block list belonging to it).
+pub(crate) fn remove_field(content: &str, key: &str) -> String {
+    let Some((fields, body)) = split(content) else {
+        return content.to_string();
+    };
+
+    let mut lines: Vec<&str> = Vec::with_capacity(fields.len());
+    let mut skipping_block = false;
+
+    for line in fields {
+        if skipping_block && (line.starts_with(' ') || line.starts_with('\t')) {
+            continue;
+        }
+        skipping_block = field_value(line, key).is_some();
+        if !skipping_block {
+            lines.push(line);
+        }
+    }
+
+    format!("---\n{}\n---\n{}", lines.join("\n"), body)
+}
+
+/// Formats a string as a double-quoted frontmatter value. Line breaks are
+/// escaped so the value stays on its line.
+pub(crate) fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r");
+    format!("\"{}\"", escaped)
+}
+
+/// Formats strings as an inline frontmatter list, e.g. `["a", "b"]`.
+pub(crate) fn format_list(values: &[String]) -> String {
+    let quoted: Vec<String> = values.iter().map(|value| quote(value)).collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+pub(crate) fn unquote(value: &str) -> String {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        let mut unescaped = String::with_capacity(value.len());
+        let mut chars = value[1..value.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                unescaped.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => unescaped.push('\n'),
+                Some('r') => unescaped.push('\r'),
+                Some('t') => unescaped.push('\t'),
+                Some(other) => unescaped.push(other),
+                None => unescaped.push('\\'),
+            }
+        }
+        unescaped
+    } else if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+        value[1..value.len() - 1].replace("''", "'")
+    } else {
+        value.to_string()
+    }
+}
+
+fn field_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    // Only top-level fields; indented lines belong to nested values
+    if line.starts_with(' ') || line.starts_with('\t') {
+        return None;
+    }
+    line.strip_prefix(key)?.strip_prefix(':')
+}
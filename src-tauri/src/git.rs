@@ -0,0 +1,700 @@
+use git2::{
+    BranchType, Cred, CredentialType, Delta, DiffFindOptions, FetchOptions, IndexAddOption,
+    PushOptions, RemoteCallbacks, Repository, Sort, Status, StatusOptions,
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GitFileState {
+    Modified,
+    Untracked,
+    Added,
+    Deleted,
+    Renamed,
+    Conflicted,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitFileStatus {
+    path: String,            // Relative to the studio folder
+    project: Option<String>, // Set when the file is a post
+    slug: Option<String>,
+    state: GitFileState,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitCommitInfo {
+    id: String,
+    summary: String,
+    author: String,
+    date: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitPullResult {
+    outcome: String, // "up_to_date", "fast_forward" or "merged"
+    head: String,
+}
+
+fn open_repo(studio_path: &Path) -> Result<Repository, String> {
+    Repository::open(studio_path).map_err(|e| {
+        format!(
+            "Studio folder {} is not a git repository: {}",
+            studio_path.display(),
+            e.message()
+        )
+    })
+}
+
+/// Resolves credentials the way the git command line would: the configured
+/// credential helper for HTTPS remotes and the SSH agent for SSH remotes.
+/// libgit2 asks again after every rejected credential, so each kind is only
+/// offered once before giving up.
+fn remote_callbacks(repo: &Repository) -> Result<RemoteCallbacks<'static>, String> {
+    let config = repo
+        .config()
+        .map_err(|e| format!("Failed to read git config: {}", e.message()))?;
+
+    let mut callbacks = RemoteCallbacks::new();
+    let mut tried = CredentialType::empty();
+    callbacks.credentials(move |url, username, allowed| {
+        let untried = allowed - tried;
+        if untried.contains(CredentialType::USERNAME) {
+            tried.insert(CredentialType::USERNAME);
+            return Cred::username(username.unwrap_or("git"));
+        }
+        if untried.contains(CredentialType::SSH_KEY) {
+            tried.insert(CredentialType::SSH_KEY);
+            return Cred::ssh_key_from_agent(username.unwrap_or("git"));
+        }
+        if untried.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            tried.insert(CredentialType::USER_PASS_PLAINTEXT);
+            return Cred::credential_helper(&config, url, username);
+        }
+        if untried.contains(CredentialType::DEFAULT) {
+            tried.insert(CredentialType::DEFAULT);
+            return Cred::default();
+        }
+        Err(git2::Error::from_str(&format!(
+            "Authentication failed for {}; check your SSH agent or git credential helper",
+            url
+        )))
+    });
+
+    Ok(callbacks)
+}
+
+fn current_branch(repo: &Repository) -> Result<String, String> {
+    let head = repo
+        .head()
+        .map_err(|e| format!("Failed to read HEAD: {}", e.message()))?;
+    head.shorthand()
+        .filter(|_| head.is_branch())
+        .map(|name| name.to_string())
+        .ok_or_else(|| "HEAD is not on a branch".to_string())
+}
+
+/// Splits `src/pages/<project>/<slug>.md` (or `.mdx`) into its project and
+/// slug. The project may be a section path such as `docs/guides`.
+fn post_for_path(studio_path: &Path, path: &str) -> (Option<String>, Option<String>) {
+    let Some((project, file)) = path
+        .strip_prefix("src/pages/")
+        .and_then(|relative| relative.rsplit_once('/'))
+    else {
+        return (None, None);
+    };
+    if crate::sections::resolve_project_path(studio_path, project).is_err() {
+        return (None, None);
+    }
+    match crate::post_format::parse_filename(file) {
+        Some((slug, _)) => (Some(project.to_string()), Some(slug.to_string())),
+        None => (None, None),
+    }
+}
+
+fn file_state(status: Status) -> Option<GitFileState> {
+    if status.is_conflicted() {
+        Some(GitFileState::Conflicted)
+    } else if status.is_wt_new() {
+        Some(GitFileState::Untracked)
+    } else if status.is_index_new() {
+        Some(GitFileState::Added)
+    } else if status.is_wt_deleted() || status.is_index_deleted() {
+        Some(GitFileState::Deleted)
+    } else if status.is_wt_renamed() || status.is_index_renamed() {
+        Some(GitFileState::Renamed)
+    } else if status.is_wt_modified()
+        || status.is_index_modified()
+        || status.is_wt_typechange()
+        || status.is_index_typechange()
+    {
+        Some(GitFileState::Modified)
+    } else {
+        None
+    }
+}
+
+fn read_status(studio_path: &Path) -> Result<Vec<GitFileStatus>, String> {
+    let repo = open_repo(studio_path)?;
+
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false);
+
+    let statuses = repo
+        .statuses(Some(&mut options))
+        .map_err(|e| format!("Failed to read git status: {}", e.message()))?;
+
+    let mut files = Vec::new();
+    for entry in statuses.iter() {
+        let (Some(path), Some(state)) = (entry.path(), file_state(entry.status())) else {
+            continue;
+        };
+        let (project, slug) = post_for_path(studio_path, path);
+        files.push(GitFileStatus {
+            path: path.to_string(),
+            project,
+            slug,
+            state,
+        });
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+#[tauri::command]
+pub async fn git_status() -> Result<Vec<GitFileStatus>, String> {
+    read_status(&crate::studio_dir()?)
+}
+
+fn commit_paths(studio_path: &Path, message: &str, paths: &[String]) -> Result<String, String> {
+    if message.trim().is_empty() {
+        return Err("Commit message cannot be empty".to_string());
+    }
+    if paths.is_empty() {
+        return Err("Select at least one file to commit".to_string());
+    }
+
+    let repo = open_repo(studio_path)?;
+    let mut index = repo
+        .index()
+        .map_err(|e| format!("Failed to open git index: {}", e.message()))?;
+
+    for path in paths {
+        if studio_path.join(path).exists() {
+            index
+                .add_all([path.as_str()], IndexAddOption::DEFAULT, None)
+                .map_err(|e| format!("Failed to stage {}: {}", path, e.message()))?;
+        } else {
+            // Stage deletions too
+            index
+                .remove_all([path.as_str()], None)
+                .map_err(|e| format!("Failed to stage removal of {}: {}", path, e.message()))?;
+        }
+    }
+    index
+        .write()
+        .map_err(|e| format!("Failed to write git index: {}", e.message()))?;
+
+    let tree_id = index
+        .write_tree()
+        .map_err(|e| format!("Failed to write tree: {}", e.message()))?;
+    let tree = repo
+        .find_tree(tree_id)
+        .map_err(|e| format!("Failed to find tree: {}", e.message()))?;
+    let signature = repo.signature().map_err(|e| {
+        format!(
+            "Set user.name and user.email in your git config: {}",
+            e.message()
+        )
+    })?;
+
+    // The first commit in a fresh repository has no parent
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+    let commit_id = repo
+        .commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message.trim(),
+            &tree,
+            &parents,
+        )
+        .map_err(|e| format!("Failed to commit: {}", e.message()))?;
+
+    Ok(commit_id.to_string())
+}
+
+#[tauri::command]
+pub async fn git_commit(message: String, paths: Vec<String>) -> Result<String, String> {
+    commit_paths(&crate::studio_dir()?, &message, &paths)
+}
+
+fn post_log(
+    studio_path: &Path,
+    project_name: &str,
+    slug: &str,
+    limit: usize,
+) -> Result<Vec<GitCommitInfo>, String> {
+    let repo = open_repo(studio_path)?;
+    // Posts deleted since are looked up as Markdown
+    let project_path = crate::sections::resolve_project_path(studio_path, project_name)?;
+    let format = crate::post_format::find_post_file(&project_path, slug)
+        .map(|(_, format)| format)
+        .unwrap_or_default();
+    // Followed back through renames and moves as the history is walked
+    let mut post_path = format!("src/pages/{}/{}", project_name, format.filename(slug));
+
+    let mut revwalk = repo
+        .revwalk()
+        .map_err(|e| format!("Failed to walk history: {}", e.message()))?;
+    if revwalk.push_head().is_err() {
+        // No commits yet
+        return Ok(vec![]);
+    }
+    // Newest first, so renames are met before the commits that predate them
+    revwalk
+        .set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+        .map_err(|e| format!("Failed to walk history: {}", e.message()))?;
+
+    let mut commits = Vec::new();
+    for oid in revwalk {
+        if commits.len() >= limit {
+            break;
+        }
+
+        let oid = oid.map_err(|e| format!("Failed to walk history: {}", e.message()))?;
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| format!("Failed to read commit: {}", e.message()))?;
+        let tree = commit
+            .tree()
+            .map_err(|e| format!("Failed to read commit tree: {}", e.message()))?;
+        let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+
+        let mut diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .map_err(|e| format!("Failed to diff commit: {}", e.message()))?;
+        diff.find_similar(Some(DiffFindOptions::new().renames(true)))
+            .map_err(|e| format!("Failed to detect renames: {}", e.message()))?;
+
+        // Deletions keep the path on both sides
+        let Some(delta) = diff
+            .deltas()
+            .find(|delta| delta.new_file().path() == Some(Path::new(&post_path)))
+        else {
+            continue;
+        };
+        if delta.status() == Delta::Renamed {
+            if let Some(old_path) = delta.old_file().path() {
+                post_path = old_path.to_string_lossy().replace('\\', "/");
+            }
+        }
+
+        let author = commit.author();
+        let date = chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+            .map(|date| date.to_rfc3339())
+            .unwrap_or_default();
+
+        commits.push(GitCommitInfo {
+            id: oid.to_string(),
+            summary: commit.summary().unwrap_or_default().to_string(),
+            author: author.name().unwrap_or_default().to_string(),
+            date,
+        });
+    }
+
+    Ok(commits)
+}
+
+#[tauri::command]
+pub async fn git_log_for_post(
+    project_name: String,
+    slug: String,
+    limit: Option<usize>,
+) -> Result<Vec<GitCommitInfo>, String> {
+    post_log(
+        &crate::studio_dir()?,
+        &project_name,
+        &slug,
+        limit.unwrap_or(50),
+    )
+}
+
+fn pull(studio_path: &Path, remote_name: &str) -> Result<GitPullResult, String> {
+    let repo = open_repo(studio_path)?;
+    let branch = current_branch(&repo)?;
+
+    let mut remote = repo
+        .find_remote(remote_name)
+        .map_err(|e| format!("Remote '{}' not found: {}", remote_name, e.message()))?;
+    let mut fetch_options = FetchOptions::new();
+    fetch_options.remote_callbacks(remote_callbacks(&repo)?);
+    remote
+        .fetch(&[&branch], Some(&mut fetch_options), None)
+        .map_err(|e| format!("Failed to fetch from '{}': {}", remote_name, e.message()))?;
+
+    let upstream = repo
+        .find_branch(&format!("{}/{}", remote_name, branch), BranchType::Remote)
+        .map_err(|e| format!("Remote has no branch '{}': {}", branch, e.message()))?;
+    let upstream_commit = upstream
+        .get()
+        .peel_to_commit()
+        .map_err(|e| format!("Failed to read remote branch: {}", e.message()))?;
+    let annotated = repo
+        .find_annotated_commit(upstream_commit.id())
+        .map_err(|e| format!("Failed to read remote branch: {}", e.message()))?;
+
+    let (analysis, _) = repo
+        .merge_analysis(&[&annotated])
+        .map_err(|e| format!("Failed to analyze merge: {}", e.message()))?;
+
+    if analysis.is_up_to_date() {
+        let head = repo.head().ok().and_then(|h| h.target());
+        return Ok(GitPullResult {
+            outcome: "up_to_date".to_string(),
+            head: head.map(|id| id.to_string()).unwrap_or_default(),
+        });
+    }
+
+    let branch_ref = format!("refs/heads/{}", branch);
+
+    if analysis.is_fast_forward() || analysis.is_unborn() {
+        // A safe checkout refuses to overwrite local edits to the same files
+        repo.checkout_tree(
+            upstream_commit.as_object(),
+            Some(git2::build::CheckoutBuilder::new().safe()),
+        )
+        .map_err(|e| format!("Local changes block the update: {}", e.message()))?;
+        repo.reference(
+            &branch_ref,
+            upstream_commit.id(),
+            true,
+            &format!("pull: fast-forward to {}", upstream_commit.id()),
+        )
+        .map_err(|e| format!("Failed to update branch: {}", e.message()))?;
+        repo.set_head(&branch_ref)
+            .map_err(|e| format!("Failed to update HEAD: {}", e.message()))?;
+
+        return Ok(GitPullResult {
+            outcome: "fast_forward".to_string(),
+            head: upstream_commit.id().to_string(),
+        });
+    }
+
+    // Diverged: merge in memory first so a conflicting pull never touches the working tree
+    let local_commit = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .map_err(|e| format!("Failed to read HEAD: {}", e.message()))?;
+    let mut merged_index = repo
+        .merge_commits(&local_commit, &upstream_commit, None)
+        .map_err(|e| format!("Failed to merge: {}", e.message()))?;
+
+    if merged_index.has_conflicts() {
+        return Err(format!(
+            "Pulling from '{}' conflicts with local commits; resolve it with git directly",
+            remote_name
+        ));
+    }
+
+    let tree_id = merged_index
+        .write_tree_to(&repo)
+        .map_err(|e| format!("Failed to write merged tree: {}", e.message()))?;
+    let tree = repo
+        .find_tree(tree_id)
+        .map_err(|e| format!("Failed to find merged tree: {}", e.message()))?;
+
+    repo.checkout_tree(
+        tree.as_object(),
+        Some(git2::build::CheckoutBuilder::new().safe()),
+    )
+    .map_err(|e| format!("Local changes block the merge: {}", e.message()))?;
+
+    let signature = repo.signature().map_err(|e| {
+        format!(
+            "Set user.name and user.email in your git config: {}",
+            e.message()
+        )
+    })?;
+    let merge_id = repo
+        .commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            &format!("Merge {}/{} into {}", remote_name, branch, branch),
+            &tree,
+            &[&local_commit, &upstream_commit],
+        )
+        .map_err(|e| format!("Failed to create merge commit: {}", e.message()))?;
+
+    Ok(GitPullResult {
+        outcome: "merged".to_string(),
+        head: merge_id.to_string(),
+    })
+}
+
+#[tauri::command]
+pub async fn git_pull(remote: Option<String>) -> Result<GitPullResult, String> {
+    let remote_name = remote.unwrap_or_else(|| "origin".to_string());
+    pull(&crate::studio_dir()?, &remote_name)
+}
+
+fn push(studio_path: &Path, remote_name: &str) -> Result<(), String> {
+    let repo = open_repo(studio_path)?;
+    let branch = current_branch(&repo)?;
+
+    let mut remote = repo
+        .find_remote(remote_name)
+        .map_err(|e| format!("Remote '{}' not found: {}", remote_name, e.message()))?;
+
+    let rejection = std::rc::Rc::new(std::cell::RefCell::new(None));
+    let mut callbacks = remote_callbacks(&repo)?;
+    let rejection_slot = rejection.clone();
+    callbacks.push_update_reference(move |_, status| {
+        if let Some(message) = status {
+            *rejection_slot.borrow_mut() = Some(message.to_string());
+        }
+        Ok(())
+    });
+
+    let mut push_options = PushOptions::new();
+    push_options.remote_callbacks(callbacks);
+
+    let refspec = format!("refs/heads/{0}:refs/heads/{0}", branch);
+    remote
+        .push(&[&refspec], Some(&mut push_options))
+        .map_err(|e| format!("Failed to push to '{}': {}", remote_name, e.message()))?;
+
+    if let Some(message) = rejection.borrow().as_ref() {
+        return Err(format!("Remote rejected the push: {}", message));
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn git_push(remote: Option<String>) -> Result<(), String> {
+    let remote_name = remote.unwrap_or_else(|| "origin".to_string());
+    push(&crate::studio_dir()?, &remote_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::RepositoryInitOptions;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("studio-git-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn set_identity(repo: &Repository) {
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Studio Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+    }
+
+    fn init_workspace(dir: &Path) -> Repository {
+        let repo =
+            Repository::init_opts(dir, RepositoryInitOptions::new().initial_head("main")).unwrap();
+        set_identity(&repo);
+        repo
+    }
+
+    fn write_post(dir: &Path, project: &str, slug: &str, body: &str) -> String {
+        let relative = format!("src/pages/{}/{}.md", project, slug);
+        let path = dir.join(&relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, format!("---\ntitle: \"{}\"\n---\n{}\n", slug, body)).unwrap();
+        relative
+    }
+
+    #[test]
+    fn status_reports_posts_with_their_project_and_slug() {
+        let dir = temp_dir("status");
+        init_workspace(&dir);
+        write_post(&dir, "blog", "hello", "Hi");
+        fs::write(dir.join("README.md"), "readme").unwrap();
+
+        let files = read_status(&dir).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "README.md");
+        assert!(files[0].project.is_none());
+        assert_eq!(files[1].path, "src/pages/blog/hello.md");
+        assert_eq!(files[1].project.as_deref(), Some("blog"));
+        assert_eq!(files[1].slug.as_deref(), Some("hello"));
+        assert!(matches!(files[1].state, GitFileState::Untracked));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn commit_stages_selected_paths_and_log_follows_the_post() {
+        let dir = temp_dir("commit");
+        init_workspace(&dir);
+        let hello = write_post(&dir, "blog", "hello", "Hi");
+        let other = write_post(&dir, "blog", "other", "Other");
+
+        assert!(commit_paths(&dir, "  ", std::slice::from_ref(&hello)).is_err());
+        assert!(commit_paths(&dir, "Add hello", &[]).is_err());
+
+        commit_paths(&dir, "Add hello", std::slice::from_ref(&hello)).unwrap();
+        let files = read_status(&dir).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, other);
+
+        commit_paths(&dir, "Add other", &[other]).unwrap();
+        write_post(&dir, "blog", "hello", "Edited");
+        commit_paths(&dir, "Edit hello", &[hello.clone()]).unwrap();
+
+        let log = post_log(&dir, "blog", "hello", 50).unwrap();
+        let summaries: Vec<&str> = log.iter().map(|commit| commit.summary.as_str()).collect();
+        assert_eq!(summaries, ["Edit hello", "Add hello"]);
+        assert_eq!(post_log(&dir, "blog", "hello", 1).unwrap().len(), 1);
+
+        // Deletions are staged too
+        fs::remove_file(dir.join(&hello)).unwrap();
+        commit_paths(&dir, "Remove hello", &[hello]).unwrap();
+        assert!(read_status(&dir).unwrap().is_empty());
+        assert_eq!(post_log(&dir, "blog", "hello", 50).unwrap().len(), 3);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn log_follows_a_post_through_renames_and_moves() {
+        let dir = temp_dir("rename");
+        init_workspace(&dir);
+        let body = "A post long enough that a new title keeps it recognizably the same file.\n\nSecond paragraph.";
+        let draft = write_post(&dir, "blog", "draft", body);
+        fs::create_dir_all(dir.join("src/pages/docs")).unwrap();
+        commit_paths(&dir, "Add draft", std::slice::from_ref(&draft)).unwrap();
+
+        // Renamed within the project, as rename_post does
+        fs::remove_file(dir.join(&draft)).unwrap();
+        let renamed = write_post(&dir, "blog", "final", body);
+        commit_paths(&dir, "Rename draft", &[draft, renamed.clone()]).unwrap();
+
+        // Moved to another project, as move_post does
+        fs::remove_file(dir.join(&renamed)).unwrap();
+        let moved = write_post(&dir, "docs", "final", body);
+        commit_paths(&dir, "Move to docs", &[renamed, moved.clone()]).unwrap();
+
+        write_post(&dir, "docs", "final", &format!("{}\n\nEdited.", body));
+        commit_paths(&dir, "Edit final", &[moved]).unwrap();
+
+        let log = post_log(&dir, "docs", "final", 50).unwrap();
+        let summaries: Vec<&str> = log.iter().map(|commit| commit.summary.as_str()).collect();
+        assert_eq!(
+            summaries,
+            ["Edit final", "Move to docs", "Rename draft", "Add draft"]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn posts_inside_sections_are_recognized() {
+        let dir = temp_dir("sections");
+        init_workspace(&dir);
+        let guide = write_post(&dir, "docs/guides", "setup", "Setup");
+        fs::create_dir_all(dir.join("src/pages/docs/guides/images")).unwrap();
+        fs::write(dir.join("src/pages/docs/guides/images/shot.png"), "png").unwrap();
+
+        let files = read_status(&dir).unwrap();
+        let post = files.iter().find(|file| file.path == guide).unwrap();
+        assert_eq!(post.project.as_deref(), Some("docs/guides"));
+        assert_eq!(post.slug.as_deref(), Some("setup"));
+        let image = files
+            .iter()
+            .find(|file| file.path.ends_with("shot.png"))
+            .unwrap();
+        assert!(image.slug.is_none());
+
+        commit_paths(&dir, "Add setup", &[guide]).unwrap();
+        assert_eq!(post_log(&dir, "docs/guides", "setup", 50).unwrap().len(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn push_and_pull_through_a_bare_remote() {
+        let root = temp_dir("remote");
+        let remote_path = root.join("remote.git");
+        Repository::init_opts(
+            &remote_path,
+            RepositoryInitOptions::new().bare(true).initial_head("main"),
+        )
+        .unwrap();
+
+        let first = root.join("first");
+        let repo = init_workspace(&first);
+        repo.remote("origin", remote_path.to_str().unwrap())
+            .unwrap();
+        let hello = write_post(&first, "blog", "hello", "Hi");
+        commit_paths(&first, "Add hello", &[hello]).unwrap();
+        push(&first, "origin").unwrap();
+        assert!(push(&first, "upstream").is_err());
+
+        let second = root.join("second");
+        set_identity(&Repository::clone(remote_path.to_str().unwrap(), &second).unwrap());
+        assert!(second.join("src/pages/blog/hello.md").exists());
+        assert_eq!(pull(&second, "origin").unwrap().outcome, "up_to_date");
+
+        let news = write_post(&second, "blog", "news", "News");
+        commit_paths(&second, "Add news", &[news]).unwrap();
+        push(&second, "origin").unwrap();
+
+        assert_eq!(pull(&first, "origin").unwrap().outcome, "fast_forward");
+        assert!(first.join("src/pages/blog/news.md").exists());
+
+        // Both sides commit different posts, so pulling merges them
+        let local = write_post(&first, "blog", "local", "Local");
+        commit_paths(&first, "Add local", &[local]).unwrap();
+        let remote = write_post(&second, "blog", "remote", "Remote");
+        commit_paths(&second, "Add remote", &[remote]).unwrap();
+        push(&second, "origin").unwrap();
+
+        // The remote moved on, so pushing without pulling is rejected
+        assert!(push(&first, "origin").is_err());
+        assert_eq!(pull(&first, "origin").unwrap().outcome, "merged");
+        assert!(first.join("src/pages/blog/remote.md").exists());
+        assert!(first.join("src/pages/blog/local.md").exists());
+        push(&first, "origin").unwrap();
+
+        // Conflicting edits to the same post leave the working tree alone
+        write_post(&first, "blog", "hello", "First edit");
+        commit_paths(
+            &first,
+            "Edit on first",
+            &["src/pages/blog/hello.md".to_string()],
+        )
+        .unwrap();
+        pull(&second, "origin").unwrap();
+        write_post(&second, "blog", "hello", "Second edit");
+        commit_paths(
+            &second,
+            "Edit on second",
+            &["src/pages/blog/hello.md".to_string()],
+        )
+        .unwrap();
+        push(&second, "origin").unwrap();
+        assert!(pull(&first, "origin").is_err());
+        let content = fs::read_to_string(first.join("src/pages/blog/hello.md")).unwrap();
+        assert!(content.contains("First edit"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
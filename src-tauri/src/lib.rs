@@ -3,11 +3,39 @@ use std::fs;
 use std::io::Write;
 use tauri::{AppHandle, Manager};
 
+mod adopt;
+mod backup;
+mod doctor;
+mod epub;
+mod feeds;
+mod frontmatter;
+mod git;
+mod links;
+mod markdown_import;
+mod ordering;
+mod post_format;
+mod post_status;
+mod project_manifest;
+mod redirects;
+mod repair;
+mod sections;
+mod site_config;
+mod site_posts;
+mod sitemap;
+mod slug;
+mod taxonomy;
+mod template;
+mod themes;
+mod validation;
+mod wordpress;
+
 #[derive(Debug, Serialize, Deserialize)]
 struct Project {
     name: String,      // Display name (what user sees)
     folder_name: String, // Actual folder name (sanitized)
     path: String,
+    #[serde(default)]
+    managed: bool, // Has the studio scaffolding; unmanaged folders can be adopted
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,6 +44,9 @@ struct Post {
     title: String,
     slug: String,
     content: String,
+    status: post_status::PostStatus,
+    publish_at: Option<String>,
+    format: post_format::PostFormat,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -48,9 +79,26 @@ async fn initialize_studio(app: AppHandle) -> Result<StudioInitResult, String> {
             is_first_time: true,
         })
     } else {
+        // Index pages from before post statuses would list drafts
+        let upgraded = repair::upgrade_index_pages(&studio_path)?;
+        let mut message = "✅ Studio workspace found!\n\nLocation: ~/Documents/studio/\n\nYour existing setup has been preserved.".to_string();
+        if !upgraded.is_empty() {
+            message.push_str(&format!(
+                "\n\nUpdated the index pages of {} so drafts stay off them.",
+                upgraded.join(", ")
+            ));
+        }
+
+        // Catch up on posts that came due and on edits made outside the app
+        let caught_up = post_status::publish_due_posts(&studio_path)
+            .and_then(|_| refresh_site_files(&studio_path));
+        if let Err(e) = caught_up {
+            message.push_str(&format!("\n\n⚠️ {}", e));
+        }
+
         Ok(StudioInitResult {
             success: true,
-            message: "✅ Studio workspace found!\n\nLocation: ~/Documents/studio/\n\nYour existing setup has been preserved.".to_string(),
+            message,
             is_first_time: false,
         })
     }
@@ -71,6 +119,15 @@ fn create_starter_site_internal(
 
     // Recursively copy all template files
     copy_dir_all(&template_path, studio_path)?;
+    template::record_template(&template_path, studio_path)?;
+
+    // The layouts come from the default theme, which records their hashes
+    themes::install_theme(
+        &themes::themes_dir(app)?,
+        studio_path,
+        themes::DEFAULT_THEME,
+        false,
+    )?;
 
     Ok(())
 }
@@ -140,6 +197,10 @@ async fn list_projects() -> Result<Vec<Project>, String> {
                     let path = entry.path();
                     if path.is_dir() {
                         if let Some(folder_name) = path.file_name().and_then(|n| n.to_str()) {
+                            if taxonomy::is_tag_pages_dir(&pages_path, folder_name) {
+                                continue;
+                            }
+
                             // Try to read display name from index.astro frontmatter
                             let display_name = read_project_display_name(&path)
                                 .unwrap_or_else(|| folder_name.to_string());
@@ -148,6 +209,7 @@ async fn list_projects() -> Result<Vec<Project>, String> {
                                 name: display_name,
                                 folder_name: folder_name.to_string(),
                                 path: path.to_string_lossy().to_string(),
+                                managed: adopt::is_managed(&path),
                             });
                         }
                     }
@@ -158,224 +220,171 @@ async fn list_projects() -> Result<Vec<Project>, String> {
     }
 
     projects.sort_by(|a, b| a.name.cmp(&b.name));
+    let order = site_config::load_site_config(&documents_dir.join("studio"))
+        .map(|config| config.project_order)
+        .unwrap_or_default();
+    projects.sort_by_key(|project| ordering::order_position(&order, &project.folder_name));
+
     Ok(projects)
 }
 
 #[tauri::command]
 async fn create_project(name: String) -> Result<Project, String> {
-    // Sanitize the project name
-    let sanitized_name = sanitize_project_name(&name);
-
-    if sanitized_name.is_empty() {
-        return Err("Project name cannot be empty after sanitization".to_string());
-    }
-
     let documents_dir = dirs::document_dir().ok_or("Could not find documents directory")?;
 
     let pages_path = documents_dir.join("studio").join("src").join("pages");
-    let project_path = pages_path.join(&sanitized_name);
 
-    // Check if project already exists
-    if project_path.exists() {
-        return Err(format!("Project '{}' already exists", sanitized_name));
-    }
+    // Sanitize the project name, rejecting reserved and taken folder names
+    let (name, sanitized_name) =
+        validation::validate(validation::NameKind::Project, &pages_path, &name, None)
+            .into_result()?;
+    let project_path = pages_path.join(&sanitized_name);
 
     // Create the project directory
     if let Err(e) = fs::create_dir_all(&project_path) {
         return Err(format!("Failed to create project directory: {}", e));
     }
 
-    // Create a project-specific layout that uses the main PostLayout
-    let layout_content = format!(
-        r#"---
-import PostLayout from '../../layouts/PostLayout.astro';
+    write_project_scaffold(&project_path, &name, &sanitized_name)?;
+    refresh_site_files(&documents_dir.join("studio"))?;
 
-export interface Props {{
-  title: string;
-}}
-
-const {{ title }} = Astro.props;
-const projectName = '{}';
----
+    Ok(Project {
+        name: name.clone(), // Original display name
+        folder_name: sanitized_name.clone(),
+        path: project_path.to_string_lossy().to_string(),
+        managed: true,
+    })
+}
 
-<PostLayout title={{title}} projectName={{projectName}}>
-  <slot />
-</PostLayout>
-"#,
-        sanitized_name
-    );
+#[tauri::command]
+async fn duplicate_project(
+    folder_name: String,
+    new_display_name: String,
+    include_posts: Option<bool>,
+) -> Result<Project, String> {
+    let studio_path = studio_dir()?;
+    let pages_path = studio_path.join("src").join("pages");
+    let source_path = sections::resolve_project_path(&studio_path, &folder_name)?;
+    let (new_display_name, sanitized_name) =
+        validation::validate(validation::NameKind::Project, &pages_path, &new_display_name, None)
+            .into_result()?;
+    let project_path = pages_path.join(&sanitized_name);
 
-    let layout_path = project_path.join("_layout.astro");
-    if let Err(e) = fs::write(&layout_path, layout_content) {
-        return Err(format!("Failed to create layout file: {}", e));
+    let include_posts = include_posts.unwrap_or(true);
+    let media_path = studio_path.join("public").join("media");
+    let source_assets = media_path.join(&folder_name);
+    let copies_media = include_posts && source_assets.exists();
+    if copies_media && media_path.join(&sanitized_name).exists() {
+        return Err(format!(
+            "Media folder public/media/{} already exists",
+            sanitized_name
+        ));
     }
 
-    // Create an index.astro file for the project listing
-    let index_content = format!(
-        r#"---
-title: "{}"
-displayName: "{}"
-import ProjectLayout from '../../layouts/ProjectLayout.astro';
-import {{ readdir }} from 'node:fs/promises';
-import path from 'node:path';
-
-const projectName = '{}';
-const displayName = '{}';
-const folderName = '{}';
-const projectDir = path.join(process.cwd(), 'src/pages', folderName);
-
-// Get all markdown posts
-let posts = [];
-try {{
-  const entries = await readdir(projectDir);
-  posts = entries
-    .filter(file => file.endsWith('.md'))
-    .map(file => {{
-      const slug = file.replace('.md', '');
-      return {{
-        slug,
-        title: slug.replace(/-/g, ' ').replace(/\b\w/g, l => l.toUpperCase()),
-        href: `/${{folderName}}/${{slug}}`
-      }};
-    }});
-}} catch (error) {{
-  console.log('No posts found yet');
-}}
----
+    copy_dir_all(&source_path, &project_path)?;
 
-<ProjectLayout title={{displayName}} projectName={{projectName}}>
-  <h2>Posts</h2>
+    if copies_media {
+        copy_dir_all(&source_assets, &media_path.join(&sanitized_name))?;
+        // The copied posts should show the copied media
+        links::apply_link_rewrites_in(
+            &project_path,
+            &format!("/media/{}", folder_name),
+            &format!("/media/{}", sanitized_name),
+        )?;
+    }
 
-  {{posts.length > 0 ? (
-    <div class="post-grid">
-      {{posts.map((post) => (
-        <a href={{post.href}} class="card post-card">
-          {{post.title}}
-        </a>
-      ))}}
-    </div>
-  ) : (
-    <div class="card">
-      <h3>No posts yet</h3>
-      <p>Create your first post using Studio Builder Desktop!</p>
-    </div>
-  )}}
-</ProjectLayout>
-"#,
-        name, // original display name for title
-        name, // original display name for displayName
-        name, // original display name for projectName (what user sees)
-        name, // original display name for displayName variable
-        sanitized_name // sanitized folder name for file system operations
-    );
+    // The copied scaffolds still carry the source project's names and paths
+    for path in sections::section_paths(&studio_path, &sanitized_name)? {
+        let section_path = pages_path.join(&path);
+        if !include_posts {
+            // Structure only: drop the copied posts but keep any other project files
+            let entries = fs::read_dir(&section_path)
+                .map_err(|e| format!("Failed to read project directory: {}", e))?;
+            for entry in entries.flatten() {
+                if post_format::is_post_file(&entry.path()) {
+                    fs::remove_file(entry.path())
+                        .map_err(|e| format!("Failed to remove copied post: {}", e))?;
+                }
+            }
+            // The copied order lists posts that are gone
+            let mut manifest = project_manifest::load_manifest(&section_path)?;
+            if !manifest.post_order.is_empty() {
+                manifest.post_order.clear();
+                project_manifest::save_manifest(&section_path, &manifest)?;
+            }
+        }
 
-    let index_path = project_path.join("index.astro");
-    if let Err(e) = fs::write(&index_path, index_content) {
-        return Err(format!("Failed to create index file: {}", e));
+        if path == sanitized_name {
+            write_project_scaffold(&section_path, &new_display_name, &path)?;
+        } else {
+            let folder = path.rsplit('/').next().unwrap_or(&path).to_string();
+            let name = read_project_display_name(&section_path).unwrap_or(folder);
+            refresh_project_scaffold(&section_path, &name, &path)?;
+        }
     }
+    refresh_site_files(&studio_path)?;
 
     Ok(Project {
-        name: name.clone(), // Original display name
-        folder_name: sanitized_name.clone(),
+        name: new_display_name,
+        folder_name: sanitized_name,
         path: project_path.to_string_lossy().to_string(),
+        managed: true,
     })
 }
 
 // Post CRUD operations
 #[tauri::command]
-async fn list_posts(project_name: String) -> Result<Vec<Post>, String> {
+async fn list_posts(
+    project_name: String,
+    status: Option<post_status::PostStatus>,
+) -> Result<Vec<Post>, String> {
     let documents_dir = dirs::document_dir().ok_or("Could not find documents directory")?;
 
-    let project_path = documents_dir
-        .join("studio")
-        .join("src")
-        .join("pages")
-        .join(&project_name);
-
-    if !project_path.exists() {
-        return Err(format!("Project '{}' does not exist", project_name));
-    }
-
-    let mut posts = Vec::new();
-
-    match fs::read_dir(&project_path) {
-        Ok(entries) => {
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    if path.is_file() {
-                        if let Some(extension) = path.extension() {
-                            if extension == "md" {
-                                if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                                    let slug = filename
-                                        .strip_suffix(".md")
-                                        .unwrap_or(filename)
-                                        .to_string();
-
-                                    // Read the file to extract the title
-                                    let content = fs::read_to_string(&path).unwrap_or_default();
-                                    let title = extract_title_from_markdown(&content, &slug);
-
-                                    posts.push(Post {
-                                        filename: filename.to_string(),
-                                        title,
-                                        slug,
-                                        content: String::new(), // Don't load full content for listing
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        Err(e) => return Err(format!("Failed to read project directory: {}", e)),
-    }
+    let project_path =
+        sections::resolve_project_path(&documents_dir.join("studio"), &project_name)?;
 
-    posts.sort_by(|a, b| a.filename.cmp(&b.filename));
-    Ok(posts)
+    collect_posts(&project_path, status)
 }
 
 #[tauri::command]
-async fn create_post(project_name: String, title: String) -> Result<Post, String> {
+async fn create_post(
+    project_name: String,
+    title: String,
+    format: Option<post_format::PostFormat>,
+) -> Result<Post, String> {
     let documents_dir = dirs::document_dir().ok_or("Could not find documents directory")?;
 
-    let project_path = documents_dir
-        .join("studio")
-        .join("src")
-        .join("pages")
-        .join(&project_name);
+    let project_path =
+        sections::resolve_project_path(&documents_dir.join("studio"), &project_name)?;
 
-    if !project_path.exists() {
-        return Err(format!("Project '{}' does not exist", project_name));
+    let format = format.unwrap_or_default();
+    if format == post_format::PostFormat::Mdx {
+        post_format::ensure_mdx_supported(&documents_dir.join("studio"))?;
     }
 
-    // Generate a unique slug
-    let base_slug = sanitize_slug(&title);
-    let mut slug = base_slug.clone();
-    let mut counter = 1;
-
-    // Ensure unique filename
-    while project_path.join(format!("{}.md", slug)).exists() {
-        slug = format!("{}-{}", base_slug, counter);
-        counter += 1;
-    }
+    // Generate a unique slug; empty and reserved ones are rejected
+    let (title, base_slug) =
+        validation::validate(validation::NameKind::Post, &project_path, &title, None)
+            .allow_collision()
+            .into_result()?;
+    let slug = unique_post_slug(&project_path, &base_slug, None);
 
-    let filename = format!("{}.md", slug);
+    let filename = format.filename(&slug);
     let file_path = project_path.join(&filename);
 
     // Create markdown content with frontmatter
     let content = format!(
         r#"---
-title: "{}"
+title: {}
 date: {}
+status: draft
 ---
 
 # {}
 
 Write your content here...
 "#,
-        title,
+        frontmatter::quote(&title),
         chrono::Utc::now().format("%Y-%m-%d"),
         title
     );
@@ -385,11 +394,16 @@ Write your content here...
         return Err(format!("Failed to create post file: {}", e));
     }
 
+    let state = post_status::read_post_state(&content);
+
     Ok(Post {
         filename,
         title,
         slug,
         content,
+        status: state.status,
+        publish_at: state.publish_at,
+        format,
     })
 }
 
@@ -397,28 +411,27 @@ Write your content here...
 async fn read_post(project_name: String, slug: String) -> Result<Post, String> {
     let documents_dir = dirs::document_dir().ok_or("Could not find documents directory")?;
 
-    let project_path = documents_dir
-        .join("studio")
-        .join("src")
-        .join("pages")
-        .join(&project_name);
-    let filename = format!("{}.md", slug);
-    let file_path = project_path.join(&filename);
-
-    if !file_path.exists() {
-        return Err(format!("Post '{}' does not exist", slug));
-    }
+    let project_path =
+        sections::resolve_project_path(&documents_dir.join("studio"), &project_name)?;
+    let (file_path, format) = post_format::find_post_file(&project_path, &slug)
+        .ok_or_else(|| format!("Post '{}' does not exist", slug))?;
+    let filename = format.filename(&slug);
 
     let content =
         fs::read_to_string(&file_path).map_err(|e| format!("Failed to read post file: {}", e))?;
 
     let title = extract_title_from_markdown(&content, &slug);
 
+    let state = post_status::read_post_state(&content);
+
     Ok(Post {
         filename,
         title,
         slug,
         content,
+        status: state.status,
+        publish_at: state.publish_at,
+        format,
     })
 }
 
@@ -426,84 +439,297 @@ async fn read_post(project_name: String, slug: String) -> Result<Post, String> {
 async fn update_post(project_name: String, slug: String, content: String) -> Result<(), String> {
     let documents_dir = dirs::document_dir().ok_or("Could not find documents directory")?;
 
-    let project_path = documents_dir
-        .join("studio")
-        .join("src")
-        .join("pages")
-        .join(&project_name);
-    let filename = format!("{}.md", slug);
+    let project_path =
+        sections::resolve_project_path(&documents_dir.join("studio"), &project_name)?;
+    let (file_path, _) = post_format::find_post_file(&project_path, &slug)
+        .ok_or_else(|| format!("Post '{}' does not exist", slug))?;
+
+    // Edits to drafts and other unpublished posts don't change the feeds or sitemap
+    let was_published = fs::read_to_string(&file_path).is_ok_and(|current| {
+        post_status::read_post_state(&current).status == post_status::PostStatus::Published
+    });
+    write_file_atomic(&file_path, &content)?;
+    if was_published
+        || post_status::read_post_state(&content).status == post_status::PostStatus::Published
+    {
+        refresh_site_files(&documents_dir.join("studio"))?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn delete_post(project_name: String, slug: String) -> Result<(), String> {
+    let documents_dir = dirs::document_dir().ok_or("Could not find documents directory")?;
+
+    let project_path =
+        sections::resolve_project_path(&documents_dir.join("studio"), &project_name)?;
+    let (file_path, _) = post_format::find_post_file(&project_path, &slug)
+        .ok_or_else(|| format!("Post '{}' does not exist", slug))?;
+
+    fs::remove_file(&file_path).map_err(|e| format!("Failed to delete post file: {}", e))?;
+    ordering::rename_in_post_order(&project_path, &slug, None)?;
+    refresh_site_files(&documents_dir.join("studio"))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn rename_post(
+    project_name: String,
+    old_slug: String,
+    new_title: String,
+    new_slug: Option<String>,
+    keep_redirect: Option<bool>,
+) -> Result<Post, String> {
+    let new_title = new_title.trim().to_string();
+    if new_title.is_empty() {
+        return Err("Post title cannot be empty".to_string());
+    }
+
+    let studio_path = studio_dir()?;
+    let project_path = sections::resolve_project_path(&studio_path, &project_name)?;
+    let (old_file_path, format) = post_format::find_post_file(&project_path, &old_slug)
+        .ok_or_else(|| format!("Post '{}' does not exist", old_slug))?;
+
+    let slug = resolve_renamed_slug(&project_path, &old_slug, &new_title, new_slug.as_deref())?;
+    let filename = format.filename(&slug);
     let file_path = project_path.join(&filename);
 
-    if !file_path.exists() {
-        return Err(format!("Post '{}' does not exist", slug));
+    // Move the assets first: a taken assets folder fails here, before any post
+    // file changes, and the move is undone if the post can't be written
+    let renames_file = slug != old_slug;
+    if renames_file {
+        move_post_assets(
+            &studio_path,
+            (&project_name, &old_slug),
+            (&project_name, &slug),
+        )?;
     }
 
-    // Atomic write: write to temp file first, then rename
-    let temp_path = project_path.join(format!(".{}.tmp", filename));
+    // Write the renamed post in full before removing the old file, so an
+    // interrupted rename leaves a duplicate rather than losing the post
+    let written = fs::read_to_string(&old_file_path)
+        .map_err(|e| format!("Failed to read post file: {}", e))
+        .map(|content| frontmatter::set_field(&content, "title", &frontmatter::quote(&new_title)))
+        .and_then(|content| write_file_atomic(&file_path, &content).map(|_| content));
+    let content = match written {
+        Ok(content) => content,
+        Err(e) => {
+            if renames_file {
+                let _ = move_post_assets(
+                    &studio_path,
+                    (&project_name, &slug),
+                    (&project_name, &old_slug),
+                );
+            }
+            return Err(e);
+        }
+    };
+
+    if renames_file {
+        fs::remove_file(&old_file_path)
+            .map_err(|e| format!("Failed to remove old post file: {}", e))?;
 
-    // Write to temp file
-    {
-        let mut temp_file = fs::File::create(&temp_path)
-            .map_err(|e| format!("Failed to create temp file: {}", e))?;
-        temp_file
-            .write_all(content.as_bytes())
-            .map_err(|e| format!("Failed to write to temp file: {}", e))?;
-        temp_file
-            .sync_all()
-            .map_err(|e| format!("Failed to sync temp file: {}", e))?;
+        ordering::rename_in_post_order(&project_path, &old_slug, Some(&slug))?;
+
+        let old_url = format!("/{}/{}", project_name, old_slug);
+        let new_url = format!("/{}/{}", project_name, slug);
+        links::apply_link_rewrites(&studio_path, &old_url, &new_url)?;
+
+        if keep_redirect.unwrap_or(false) {
+            redirects::record_redirect(&studio_path, &old_url, &new_url, false)?;
+        }
     }
 
-    // Atomically replace the original file
-    fs::rename(&temp_path, &file_path)
-        .map_err(|e| format!("Failed to replace original file: {}", e))?;
+    refresh_site_files(&studio_path)?;
 
-    Ok(())
+    let state = post_status::read_post_state(&content);
+
+    Ok(Post {
+        filename,
+        title: new_title,
+        slug,
+        content,
+        status: state.status,
+        publish_at: state.publish_at,
+        format,
+    })
 }
 
 #[tauri::command]
-async fn delete_post(project_name: String, slug: String) -> Result<(), String> {
-    let documents_dir = dirs::document_dir().ok_or("Could not find documents directory")?;
+async fn move_post(from_project: String, slug: String, to_project: String) -> Result<Post, String> {
+    if from_project == to_project {
+        return Err("Post is already in this project".to_string());
+    }
 
-    let project_path = documents_dir
-        .join("studio")
-        .join("src")
-        .join("pages")
-        .join(&project_name);
-    let filename = format!("{}.md", slug);
-    let file_path = project_path.join(&filename);
+    let studio_path = studio_dir()?;
+    let from_path = sections::resolve_project_path(&studio_path, &from_project)?;
+    let to_path = sections::resolve_project_path(&studio_path, &to_project)?;
+    let (old_file_path, format) = post_format::find_post_file(&from_path, &slug)
+        .ok_or_else(|| format!("Post '{}' does not exist", slug))?;
+
+    // Keep the slug unless the destination already has a post with it
+    let new_slug = unique_post_slug(&to_path, &slug, None);
+    let filename = format.filename(&new_slug);
+    let file_path = to_path.join(&filename);
+
+    // Move the assets first: a taken assets folder fails here, before any post
+    // file changes, and the move is undone if the post can't be written
+    move_post_assets(
+        &studio_path,
+        (&from_project, &slug),
+        (&to_project, &new_slug),
+    )?;
+
+    let written = fs::read_to_string(&old_file_path)
+        .map_err(|e| format!("Failed to read post file: {}", e))
+        .and_then(|content| write_file_atomic(&file_path, &content));
+    if let Err(e) = written {
+        let _ = move_post_assets(
+            &studio_path,
+            (&to_project, &new_slug),
+            (&from_project, &slug),
+        );
+        return Err(e);
+    }
+    fs::remove_file(&old_file_path)
+        .map_err(|e| format!("Failed to remove old post file: {}", e))?;
+
+    ordering::rename_in_post_order(&from_path, &slug, None)?;
+
+    let old_url = format!("/{}/{}", from_project, slug);
+    let new_url = format!("/{}/{}", to_project, new_slug);
+    links::apply_link_rewrites(&studio_path, &old_url, &new_url)?;
+    redirects::record_redirect(&studio_path, &old_url, &new_url, false)?;
+
+    refresh_site_files(&studio_path)?;
+
+    // Re-read: the post's own asset links may have been rewritten
+    let content =
+        fs::read_to_string(&file_path).map_err(|e| format!("Failed to read post file: {}", e))?;
+    let title = extract_title_from_markdown(&content, &new_slug);
+
+    let state = post_status::read_post_state(&content);
+
+    Ok(Post {
+        filename,
+        title,
+        slug: new_slug,
+        content,
+        status: state.status,
+        publish_at: state.publish_at,
+        format,
+    })
+}
 
-    if !file_path.exists() {
-        return Err(format!("Post '{}' does not exist", slug));
+#[tauri::command]
+async fn duplicate_post(project_name: String, slug: String) -> Result<Post, String> {
+    let studio_path = studio_dir()?;
+    let project_path = sections::resolve_project_path(&studio_path, &project_name)?;
+    let (source_file_path, format) = post_format::find_post_file(&project_path, &slug)
+        .ok_or_else(|| format!("Post '{}' does not exist", slug))?;
+
+    let content = fs::read_to_string(&source_file_path)
+        .map_err(|e| format!("Failed to read post file: {}", e))?;
+    let title = format!("{} (Copy)", extract_title_from_markdown(&content, &slug));
+
+    let base_slug = slug::title_slug(&title, &slug::workspace_settings());
+    let new_slug = unique_post_slug(&project_path, &base_slug, None);
+    let filename = format.filename(&new_slug);
+    let content = frontmatter::set_field(&content, "title", &frontmatter::quote(&title));
+
+    write_file_atomic(&project_path.join(&filename), &content)?;
+
+    let source_assets = post_assets_dir(&studio_path, &project_name, &slug);
+    if source_assets.exists() {
+        copy_dir_all(
+            &source_assets,
+            &post_assets_dir(&studio_path, &project_name, &new_slug),
+        )?;
     }
 
-    fs::remove_file(&file_path).map_err(|e| format!("Failed to delete post file: {}", e))?;
+    refresh_site_files(&studio_path)?;
 
-    Ok(())
+    let state = post_status::read_post_state(&content);
+
+    Ok(Post {
+        filename,
+        title,
+        slug: new_slug,
+        content,
+        status: state.status,
+        publish_at: state.publish_at,
+        format,
+    })
 }
 
 #[tauri::command]
-async fn rename_project(old_folder_name: String, new_display_name: String) -> Result<Project, String> {
-    // Sanitize the new project name for folder
-    let sanitized_new_name = sanitize_project_name(&new_display_name);
+async fn preview_post_rename(
+    project_name: String,
+    old_slug: String,
+    new_title: String,
+    new_slug: Option<String>,
+) -> Result<Vec<links::LinkRewrite>, String> {
+    let studio_path = studio_dir()?;
+    let project_path = sections::resolve_project_path(&studio_path, &project_name)?;
+
+    if !post_format::post_exists(&project_path, &old_slug) {
+        return Err(format!("Post '{}' does not exist", old_slug));
+    }
 
-    if sanitized_new_name.is_empty() {
-        return Err("Project name cannot be empty after sanitization".to_string());
+    let slug = resolve_renamed_slug(
+        &project_path,
+        &old_slug,
+        new_title.trim(),
+        new_slug.as_deref(),
+    )?;
+    if slug == old_slug {
+        return Ok(vec![]);
     }
 
+    links::preview_link_rewrites(
+        &studio_path,
+        &format!("/{}/{}", project_name, old_slug),
+        &format!("/{}/{}", project_name, slug),
+    )
+}
+
+#[tauri::command]
+async fn rename_project(old_folder_name: String, new_display_name: String) -> Result<Project, String> {
     let documents_dir = dirs::document_dir().ok_or("Could not find documents directory")?;
     let pages_path = documents_dir.join("studio").join("src").join("pages");
     
-    let old_project_path = pages_path.join(&old_folder_name);
-    let new_project_path = pages_path.join(&sanitized_new_name);
-
-    // Check if old project exists
-    if !old_project_path.exists() {
-        return Err(format!("Project folder '{}' does not exist", old_folder_name));
+    let old_project_path =
+        sections::resolve_project_path(&documents_dir.join("studio"), &old_folder_name)?;
+    if old_folder_name.contains('/') {
+        return Err(format!(
+            "'{}' is a section; rename it with its project",
+            old_folder_name
+        ));
     }
 
-    // Check if new project name already exists (only if folder name is changing)
-    if old_folder_name != sanitized_new_name && new_project_path.exists() {
-        return Err(format!("Project folder '{}' already exists", sanitized_new_name));
+    // Sanitize the new project name for folder; the project may keep its own
+    let (new_display_name, sanitized_new_name) = validation::validate(
+        validation::NameKind::Project,
+        &pages_path,
+        &new_display_name,
+        Some(&old_folder_name),
+    )
+    .into_result()?;
+    let new_project_path = pages_path.join(&sanitized_new_name);
+
+    // The project's media folder moves with it, so its new name must be free too
+    let studio_path = documents_dir.join("studio");
+    let media_path = studio_path.join("public").join("media");
+    let old_media_path = media_path.join(&old_folder_name);
+    let new_media_path = media_path.join(&sanitized_new_name);
+    let moves_media = old_folder_name != sanitized_new_name && old_media_path.exists();
+    if moves_media && new_media_path.exists() {
+        return Err(format!(
+            "Media folder public/media/{} already exists",
+            sanitized_new_name
+        ));
     }
 
     // Update the index.astro file with new display name
@@ -524,27 +750,527 @@ async fn rename_project(old_folder_name: String, new_display_name: String) -> Re
                 .map_err(|e| format!("Failed to read layout file: {}", e))?;
 
             let updated_content = layout_content.replace(
-                &format!("const projectName = '{}';", old_folder_name),
-                &format!("const projectName = '{}';", sanitized_new_name),
+                &format!("const projectName = {};", js_string(&old_folder_name)),
+                &format!("const projectName = {};", js_string(&sanitized_new_name)),
             );
 
             fs::write(&layout_file_path, updated_content)
                 .map_err(|e| format!("Failed to update layout file: {}", e))?;
         }
+
+        if moves_media {
+            fs::rename(&old_media_path, &new_media_path)
+                .map_err(|e| format!("Failed to rename project media folder: {}", e))?;
+        }
+
+        // Section scaffolds hold their path, e.g. folderName = '<project>/<section>'
+        for path in sections::section_paths(&studio_path, &sanitized_new_name)?
+            .into_iter()
+            .skip(1)
+        {
+            let section_path = pages_path.join(&path);
+            let folder = path.rsplit('/').next().unwrap_or(&path);
+            let name =
+                read_project_display_name(&section_path).unwrap_or_else(|| folder.to_string());
+            refresh_project_scaffold(&section_path, &name, &path)?;
+        }
+
+        // Point internal links, media links and published URLs at the new folder
+        let old_url = format!("/{}", old_folder_name);
+        let new_url = format!("/{}", sanitized_new_name);
+        links::apply_folder_rewrites(&studio_path, &old_folder_name, &sanitized_new_name)?;
+        redirects::record_redirect(&studio_path, &old_url, &new_url, true)?;
+        ordering::rename_in_project_order(&studio_path, &old_folder_name, &sanitized_new_name)?;
+        refresh_site_files(&studio_path)?;
     }
 
     // Return the updated project information
     let final_folder_name = sanitized_new_name;
     let final_project_path = pages_path.join(&final_folder_name);
+
+    let mut manifest = project_manifest::load_manifest(&final_project_path)?;
+    manifest.name = Some(new_display_name.clone());
+    project_manifest::save_manifest(&final_project_path, &manifest)?;
     
     Ok(Project {
         name: new_display_name,
         folder_name: final_folder_name,
         path: final_project_path.to_string_lossy().to_string(),
+        managed: true,
     })
 }
 
+#[tauri::command]
+async fn preview_project_rename(
+    old_folder_name: String,
+    new_display_name: String,
+) -> Result<Vec<links::LinkRewrite>, String> {
+    let studio_path = studio_dir()?;
+    sections::resolve_project_path(&studio_path, &old_folder_name)?;
+    let (_, sanitized_new_name) = validation::validate(
+        validation::NameKind::Project,
+        &studio_path.join("src").join("pages"),
+        &new_display_name,
+        Some(&old_folder_name),
+    )
+    .into_result()?;
+
+    if old_folder_name == sanitized_new_name {
+        return Ok(vec![]);
+    }
+
+    links::preview_folder_rewrites(&studio_path, &old_folder_name, &sanitized_new_name)
+}
+
 // Helper functions
+/// Reads the posts directly inside `project_path` in listing order, without their content.
+fn collect_posts(
+    project_path: &std::path::Path,
+    status: Option<post_status::PostStatus>,
+) -> Result<Vec<Post>, String> {
+    let mut posts = Vec::new();
+
+    match fs::read_dir(project_path) {
+        Ok(entries) => {
+            for entry in entries {
+                if let Ok(entry) = entry {
+                    let path = entry.path();
+                    if path.is_file() {
+                        if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
+                            if let Some((slug, format)) = post_format::parse_filename(filename) {
+                                let slug = slug.to_string();
+
+                                // Read the file to extract the title
+                                let content = fs::read_to_string(&path).unwrap_or_default();
+                                let title = extract_title_from_markdown(&content, &slug);
+                                let state = post_status::read_post_state(&content);
+
+                                if status.is_some_and(|status| status != state.status) {
+                                    continue;
+                                }
+
+                                posts.push(Post {
+                                    filename: filename.to_string(),
+                                    title,
+                                    slug,
+                                    content: String::new(), // Don't load full content for listing
+                                    status: state.status,
+                                    publish_at: state.publish_at,
+                                    format,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Err(e) => return Err(format!("Failed to read project directory: {}", e)),
+    }
+
+    // Filename order, then the author's explicit order where one is stored
+    posts.sort_by(|a, b| a.filename.cmp(&b.filename));
+    let order = project_manifest::load_manifest(project_path)
+        .map(|manifest| manifest.post_order)
+        .unwrap_or_default();
+    posts.sort_by_key(|post| ordering::order_position(&order, &post.slug));
+
+    Ok(posts)
+}
+
+/// Writes the `_layout.astro` and `index.astro` files every studio project gets,
+/// and records the display name in the project manifest.
+/// Sections pass their path below src/pages (e.g. `docs/guides`) as `sanitized_name`.
+fn write_project_scaffold(
+    project_path: &std::path::Path,
+    name: &str,
+    sanitized_name: &str,
+) -> Result<(), String> {
+    for (filename, content) in render_project_scaffold(project_path, name, sanitized_name) {
+        if let Err(e) = fs::write(project_path.join(filename), content) {
+            return Err(format!("Failed to create {}: {}", filename, e));
+        }
+    }
+
+    let mut manifest = project_manifest::load_manifest(project_path)?;
+    manifest.name = Some(name.to_string());
+    project_manifest::save_manifest(project_path, &manifest)
+}
+
+/// Regenerates the scaffold files the folder already has. Asset folders and
+/// sections with an index page of their own get nothing new.
+fn refresh_project_scaffold(
+    project_path: &std::path::Path,
+    name: &str,
+    sanitized_name: &str,
+) -> Result<(), String> {
+    for (filename, content) in render_project_scaffold(project_path, name, sanitized_name) {
+        if project_path.join(filename).exists() {
+            write_file_atomic(&project_path.join(filename), &content)?;
+        }
+    }
+    Ok(())
+}
+
+/// A single-quoted JavaScript string literal for `text`.
+fn js_string(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('\'', "\\'")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r");
+    format!("'{}'", escaped)
+}
+
+/// The scaffold files for a project, as `(filename, content)` pairs.
+fn render_project_scaffold(
+    project_path: &std::path::Path,
+    name: &str,
+    sanitized_name: &str,
+) -> [(&'static str, String); 2] {
+    // Relative path from the folder back to src/
+    let src_prefix = "../".repeat(sanitized_name.split('/').count() + 1);
+
+    // Create a project-specific layout that uses the main PostLayout
+    let layout_content = format!(
+        r#"---
+import PostLayout from '{src}layouts/PostLayout.astro';
+
+export interface Props {{
+  title: string;
+}}
+
+const {{ title }} = Astro.props;
+const projectName = {};
+---
+
+<PostLayout title={{title}} projectName={{projectName}}>
+  <slot />
+</PostLayout>
+"#,
+        js_string(sanitized_name),
+        src = src_prefix
+    );
+
+    // Duplicated and repaired projects keep the order stored in their manifest
+    let post_order = project_manifest::load_manifest(project_path)
+        .map(|manifest| manifest.post_order)
+        .unwrap_or_default();
+
+    // Create an index.astro file for the project listing
+    let index_content = format!(
+        r#"---
+title: {}
+displayName: {}
+import ProjectLayout from '{src}layouts/ProjectLayout.astro';
+import {{ readdir, readFile }} from 'node:fs/promises';
+import path from 'node:path';
+
+const projectName = {};
+const displayName = {};
+const folderName = {};
+const projectDir = path.join(process.cwd(), 'src/pages', folderName);
+
+// Post order set in Studio Builder Desktop; unlisted posts follow by filename
+{}
+const orderPosition = (slug) => {{
+  const index = postOrder.indexOf(slug);
+  return index === -1 ? postOrder.length : index;
+}};
+
+// Read a top-level field from a post's frontmatter
+const frontmatterField = (source, key) => {{
+  const block = source.match(/^---\r?\n([\s\S]*?)\r?\n---/)?.[1] ?? '';
+  const line = block.split(/\r?\n/).find(l => l.startsWith(`${{key}}:`));
+  return line?.slice(key.length + 1).trim().replace(/^["']|["']$/g, '');
+}};
+
+// Drafts and posts in review stay off the index; scheduled posts appear
+// once their publishAt has passed when the site is built
+const isPublished = (source) => {{
+  const status = frontmatterField(source, 'status') ?? 'published';
+  if (status === 'published') return true;
+  if (status !== 'scheduled') return false;
+  const publishAt = new Date(frontmatterField(source, 'publishAt'));
+  return !Number.isNaN(publishAt.getTime()) && publishAt <= new Date();
+}};
+
+// Get all published Markdown and MDX posts
+let posts = [];
+try {{
+  const entries = await readdir(projectDir);
+  const files = entries.filter(file => /\.mdx?$/.test(file));
+  const sources = await Promise.all(
+    files.map(file => readFile(path.join(projectDir, file), 'utf-8'))
+  );
+  posts = files
+    .filter((file, i) => isPublished(sources[i]))
+    .map(file => {{
+      const slug = file.replace(/\.mdx?$/, '');
+      return {{
+        slug,
+        title: slug.replace(/-/g, ' ').replace(/\b\w/g, l => l.toUpperCase()),
+        href: `/${{folderName}}/${{slug}}`
+      }};
+    }})
+    .sort((a, b) => orderPosition(a.slug) - orderPosition(b.slug) || a.slug.localeCompare(b.slug));
+}} catch (error) {{
+  console.log('No posts found yet');
+}}
+
+// Sub-sections are folders with their own index page; image folders have none
+let sections = [];
+try {{
+  const entries = await readdir(projectDir, {{ withFileTypes: true }});
+  const folders = entries.filter(entry => entry.isDirectory() && !/^[._]/.test(entry.name));
+  const found = await Promise.all(folders.map(async (entry) => {{
+    const files = await readdir(path.join(projectDir, entry.name)).catch(() => []);
+    if (!files.some(file => /^index\./.test(file))) return null;
+    const index = await readFile(path.join(projectDir, entry.name, 'index.astro'), 'utf-8').catch(() => '');
+    return {{
+      title: frontmatterField(index, 'displayName') ?? entry.name,
+      href: `/${{folderName}}/${{entry.name}}`
+    }};
+  }}));
+  sections = found.filter(Boolean);
+  sections.sort((a, b) => a.title.localeCompare(b.title));
+}} catch (error) {{
+  console.log('No sections found');
+}}
+---
+
+<ProjectLayout title={{displayName}} projectName={{projectName}}>
+  {{sections.length > 0 && (
+    <>
+      <h2>Sections</h2>
+      <div class="post-grid">
+        {{sections.map((section) => (
+          <a href={{section.href}} class="card post-card">
+            {{section.title}}
+          </a>
+        ))}}
+      </div>
+    </>
+  )}}
+
+  <h2>Posts</h2>
+
+  {{posts.length > 0 ? (
+    <div class="post-grid">
+      {{posts.map((post) => (
+        <a href={{post.href}} class="card post-card">
+          {{post.title}}
+        </a>
+      ))}}
+    </div>
+  ) : (
+    <div class="card">
+      <h3>No posts yet</h3>
+      <p>Create your first post using Studio Builder Desktop!</p>
+    </div>
+  )}}
+</ProjectLayout>
+"#,
+        frontmatter::quote(name), // original display name for title
+        frontmatter::quote(name), // original display name for displayName
+        js_string(name), // original display name for projectName (what user sees)
+        js_string(name), // original display name for displayName variable
+        js_string(sanitized_name), // sanitized folder name for file system operations
+        ordering::post_order_declaration(&post_order),
+        src = src_prefix
+    );
+
+    [
+        ("_layout.astro", layout_content),
+        ("index.astro", index_content),
+    ]
+}
+
+pub(crate) fn studio_dir() -> Result<std::path::PathBuf, String> {
+    let documents_dir = dirs::document_dir().ok_or("Could not find documents directory")?;
+    Ok(documents_dir.join("studio"))
+}
+
+/// Folder for a post's images and attachments, served from `/media/<project>/<slug>/`.
+pub(crate) fn post_assets_dir(
+    studio_path: &std::path::Path,
+    project_name: &str,
+    slug: &str,
+) -> std::path::PathBuf {
+    studio_path
+        .join("public")
+        .join("media")
+        .join(project_name)
+        .join(slug)
+}
+
+/// Moves a post's assets folder, if it has one, and rewrites links to the old location.
+fn move_post_assets(
+    studio_path: &std::path::Path,
+    from: (&str, &str),
+    to: (&str, &str),
+) -> Result<(), String> {
+    let old_assets = post_assets_dir(studio_path, from.0, from.1);
+    if !old_assets.exists() {
+        return Ok(());
+    }
+
+    let new_assets = post_assets_dir(studio_path, to.0, to.1);
+    if new_assets.exists() {
+        return Err(format!(
+            "Assets folder {} already exists",
+            new_assets.display()
+        ));
+    }
+    if let Some(parent) = new_assets.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create assets directory: {}", e))?;
+    }
+
+    fs::rename(&old_assets, &new_assets)
+        .map_err(|e| format!("Failed to move post assets: {}", e))?;
+
+    links::apply_link_rewrites(
+        studio_path,
+        &format!("/media/{}/{}", from.0, from.1),
+        &format!("/media/{}/{}", to.0, to.1),
+    )?;
+
+    Ok(())
+}
+
+/// Appends `-1`, `-2`, ... to `base_slug` until no post file uses it. `current_slug`
+/// is the post being renamed, which may keep its own slug.
+fn unique_post_slug(
+    project_path: &std::path::Path,
+    base_slug: &str,
+    current_slug: Option<&str>,
+) -> String {
+    let mut slug = base_slug.to_string();
+    let mut counter = 1;
+
+    while validation::find_collision(project_path, &slug, current_slug).is_some() {
+        slug = format!("{}-{}", base_slug, counter);
+        counter += 1;
+    }
+
+    slug
+}
+
+/// An explicitly requested slug must be free; one derived from the title is made unique.
+fn resolve_renamed_slug(
+    project_path: &std::path::Path,
+    old_slug: &str,
+    new_title: &str,
+    requested_slug: Option<&str>,
+) -> Result<String, String> {
+    match requested_slug {
+        Some(requested) => {
+            let (_, slug) = validation::validate(
+                validation::NameKind::Slug,
+                project_path,
+                requested,
+                Some(old_slug),
+            )
+            .into_result()?;
+            Ok(slug)
+        }
+        None => {
+            let (_, base_slug) = validation::validate(
+                validation::NameKind::Post,
+                project_path,
+                new_title,
+                Some(old_slug),
+            )
+            .allow_collision()
+            .into_result()?;
+            Ok(unique_post_slug(project_path, &base_slug, Some(old_slug)))
+        }
+    }
+}
+
+/// Writes `content` to a sibling `.<name>.tmp` file, syncs it, then renames it over `path`.
+pub(crate) fn write_file_atomic(path: &std::path::Path, content: &str) -> Result<(), String> {
+    let parent = path.parent().ok_or("Invalid file path")?;
+    let filename = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid file name")?;
+    let temp_path = parent.join(format!(".{}.tmp", filename));
+
+    // Write to temp file
+    {
+        let mut temp_file = fs::File::create(&temp_path)
+            .map_err(|e| format!("Failed to create temp file: {}", e))?;
+        temp_file
+            .write_all(content.as_bytes())
+            .map_err(|e| format!("Failed to write to temp file: {}", e))?;
+        temp_file
+            .sync_all()
+            .map_err(|e| format!("Failed to sync temp file: {}", e))?;
+    }
+
+    // Atomically replace the original file
+    fs::rename(&temp_path, path).map_err(|e| format!("Failed to replace original file: {}", e))?;
+
+    Ok(())
+}
+
+/// Regenerates the feeds and sitemap after posts change. Does nothing until a
+/// site URL is configured. On failure the edit that triggered it is already saved.
+pub(crate) fn refresh_site_files(studio_path: &std::path::Path) -> Result<(), String> {
+    let configured = site_config::load_site_config(studio_path)
+        .map(|config| config.base_url().is_some())
+        .unwrap_or(false);
+    if !configured {
+        return Ok(());
+    }
+
+    feeds::generate_all_feeds(studio_path)
+        .map_err(|e| format!("Failed to regenerate feeds: {}", e))?;
+    // A hand-written sitemap is left alone; generate_sitemap reports it
+    if !sitemap::is_hand_written_sitemap(studio_path) {
+        sitemap::write_sitemap(studio_path)
+            .map_err(|e| format!("Failed to regenerate the sitemap: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Slugs of the project's posts in listing order.
+pub(crate) fn ordered_post_slugs(project_path: &std::path::Path) -> Result<Vec<String>, String> {
+    let entries = fs::read_dir(project_path)
+        .map_err(|e| format!("Failed to read project directory: {}", e))?;
+
+    let mut filenames: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| post_format::parse_filename(name).is_some())
+        .collect();
+    filenames.sort();
+
+    let order = project_manifest::load_manifest(project_path)
+        .map(|manifest| manifest.post_order)
+        .unwrap_or_default();
+    let mut slugs: Vec<String> = filenames
+        .iter()
+        .filter_map(|name| post_format::parse_filename(name))
+        .map(|(slug, _)| slug.to_string())
+        .collect();
+    slugs.dedup();
+    slugs.sort_by_key(|slug| ordering::order_position(&order, slug));
+
+    Ok(slugs)
+}
+
+/// Escapes text for use in XML element content and attribute values.
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
 fn update_project_display_name(
     index_path: &std::path::Path,
     new_display_name: &str,
@@ -580,28 +1306,28 @@ fn update_project_display_name(
     for i in 1..frontmatter_end {
         let line = &lines[i];
         if line.trim().starts_with("title:") {
-            lines[i] = format!("title: \"{}\"", new_display_name);
+            lines[i] = format!("title: {}", frontmatter::quote(new_display_name));
             found_title = true;
         } else if line.trim().starts_with("displayName:") {
-            lines[i] = format!("displayName: \"{}\"", new_display_name);
+            lines[i] = format!("displayName: {}", frontmatter::quote(new_display_name));
             found_display_name = true;
         } else if line.trim().starts_with("const projectName =") {
-            lines[i] = format!("const projectName = '{}';", new_display_name);
+            lines[i] = format!("const projectName = {};", js_string(new_display_name));
         } else if line.trim().starts_with("const displayName =") {
-            lines[i] = format!("const displayName = '{}';", new_display_name);
+            lines[i] = format!("const displayName = {};", js_string(new_display_name));
         } else if line.trim().starts_with("const folderName =") {
-            lines[i] = format!("const folderName = '{}';", new_folder_name);
+            lines[i] = format!("const folderName = {};", js_string(new_folder_name));
         }
     }
 
     // Add missing frontmatter fields if needed
     if !found_title {
-        lines.insert(1, format!("title: \"{}\"", new_display_name));
+        lines.insert(1, format!("title: {}", frontmatter::quote(new_display_name)));
         frontmatter_end += 1;
     }
     if !found_display_name {
         let insert_pos = if found_title { 2 } else { 1 };
-        lines.insert(insert_pos, format!("displayName: \"{}\"", new_display_name));
+        lines.insert(insert_pos, format!("displayName: {}", frontmatter::quote(new_display_name)));
     }
 
     let updated_content = lines.join("\n");
@@ -614,7 +1340,8 @@ fn update_project_display_name(
 fn read_project_display_name(project_path: &std::path::Path) -> Option<String> {
     let index_path = project_path.join("index.astro");
     if !index_path.exists() {
-        return None;
+        // The manifest keeps the name until repair_project restores the page
+        return manifest_display_name(project_path);
     }
 
     if let Ok(content) = fs::read_to_string(&index_path) {
@@ -639,23 +1366,20 @@ fn read_project_display_name(project_path: &std::path::Path) -> Option<String> {
                 for i in 1..frontmatter_end {
                     let line = lines[i].trim();
                     if line.starts_with("displayName:") {
-                        let display_name_part = &line[12..].trim();
-                        // Remove quotes if present
-                        let display_name = if (display_name_part.starts_with('"') && display_name_part.ends_with('"'))
-                            || (display_name_part.starts_with('\'') && display_name_part.ends_with('\''))
-                        {
-                            &display_name_part[1..display_name_part.len() - 1]
-                        } else {
-                            display_name_part
-                        };
-                        return Some(display_name.to_string());
+                        // Remove quotes and escapes if present
+                        return Some(frontmatter::unquote(line[12..].trim()));
                     }
                 }
             }
         }
     }
 
-    None
+    // Adopted folders may keep an index.astro of their own without one
+    manifest_display_name(project_path)
+}
+
+fn manifest_display_name(project_path: &std::path::Path) -> Option<String> {
+    project_manifest::load_manifest(project_path).ok()?.name
 }
 
 fn extract_title_from_markdown(content: &str, fallback_slug: &str) -> String {
@@ -690,44 +1414,7 @@ fn extract_title_from_markdown(content: &str, fallback_slug: &str) -> String {
 }
 
 fn extract_frontmatter_title(content: &str) -> Option<String> {
-    if !content.starts_with("---") {
-        return None;
-    }
-
-    let lines: Vec<&str> = content.lines().collect();
-    let mut in_frontmatter = false;
-    let mut frontmatter_end = 0;
-
-    for (i, line) in lines.iter().enumerate() {
-        if i == 0 && line.trim() == "---" {
-            in_frontmatter = true;
-            continue;
-        }
-        if in_frontmatter && line.trim() == "---" {
-            frontmatter_end = i;
-            break;
-        }
-    }
-
-    if frontmatter_end > 0 {
-        for i in 1..frontmatter_end {
-            let line = lines[i].trim();
-            if line.starts_with("title:") {
-                let title_part = &line[6..].trim();
-                // Remove quotes if present
-                let title = if (title_part.starts_with('"') && title_part.ends_with('"'))
-                    || (title_part.starts_with('\'') && title_part.ends_with('\''))
-                {
-                    &title_part[1..title_part.len() - 1]
-                } else {
-                    title_part
-                };
-                return Some(title.to_string());
-            }
-        }
-    }
-
-    None
+    frontmatter::get_field(content, "title")
 }
 
 fn sanitize_slug(title: &str) -> String {
@@ -771,13 +1458,81 @@ pub fn run() {
             create_starter_site,
             list_projects,
             create_project,
+            duplicate_project,
             list_posts,
             create_post,
             read_post,
             update_post,
             delete_post,
-            rename_project
+            rename_post,
+            move_post,
+            duplicate_post,
+            post_status::set_post_status,
+            post_status::publish_scheduled_posts,
+            preview_post_rename,
+            rename_project,
+            preview_project_rename,
+            redirects::list_redirects,
+            redirects::add_redirect,
+            redirects::remove_redirect,
+            git::git_status,
+            git::git_commit,
+            git::git_log_for_post,
+            git::git_pull,
+            git::git_push,
+            backup::export_workspace_backup,
+            backup::restore_workspace_backup,
+            wordpress::import_wordpress,
+            markdown_import::import_markdown_folder,
+            project_manifest::get_project_metadata,
+            project_manifest::update_project_metadata,
+            epub::export_project_epub,
+            feeds::generate_feeds,
+            sitemap::generate_sitemap,
+            taxonomy::list_tags,
+            taxonomy::posts_by_tag,
+            taxonomy::rename_tag,
+            taxonomy::merge_tags,
+            taxonomy::scaffold_tag_pages,
+            ordering::reorder_posts,
+            ordering::reorder_projects,
+            sections::list_project_tree,
+            sections::create_section,
+            post_format::convert_post,
+            site_config::get_site_config,
+            site_config::update_site_config,
+            themes::list_themes,
+            themes::preview_theme,
+            themes::apply_theme,
+            template::check_template_updates,
+            template::upgrade_template,
+            doctor::doctor,
+            repair::preview_project_repair,
+            repair::repair_project,
+            adopt::adopt_project,
+            validation::validate_name,
+            slug::preview_slug
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_titles_read_back_as_written() {
+        let title = r#"The "best" C:\ paths"#;
+        let content = format!("---\ntitle: {}\n---\n\nBody\n", frontmatter::quote(title));
+        assert_eq!(extract_title_from_markdown(&content, "fallback"), title);
+
+        // Writing a read title back doesn't pile up escapes
+        let copy = frontmatter::set_field(
+            &content,
+            "title",
+            &frontmatter::quote(&extract_title_from_markdown(&content, "fallback")),
+        );
+        assert_eq!(copy, content);
+    }
+}
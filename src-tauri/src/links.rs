@@ -0,0 +1,262 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Contexts in which a root-relative URL counts as an internal link. A bare `<`
+// is left out: Markdown autolinks need a scheme, and `</section>` is a closing tag.
+const LINK_PREFIXES: [&str; 6] = ["](", "]: ", "href=\"", "href='", "src=\"", "src='"];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkRewrite {
+    path: String, // Path relative to the studio folder
+    occurrences: usize,
+}
+
+struct PendingRewrite {
+    path: PathBuf,
+    content: String,
+    occurrences: usize,
+}
+
+/// Lists the files whose links to `old_url` would be rewritten, without touching them.
+pub(crate) fn preview_link_rewrites(
+    studio_path: &Path,
+    old_url: &str,
+    new_url: &str,
+) -> Result<Vec<LinkRewrite>, String> {
+    let pending = collect_rewrites(studio_path, &[(old_url, new_url)])?;
+    Ok(to_report(studio_path, &pending))
+}
+
+/// The URL pairs a renamed project folder needs: its pages and its media.
+fn folder_urls(old_folder: &str, new_folder: &str) -> [(String, String); 2] {
+    [
+        (format!("/{}", old_folder), format!("/{}", new_folder)),
+        (
+            format!("/media/{}", old_folder),
+            format!("/media/{}", new_folder),
+        ),
+    ]
+}
+
+/// Lists the files whose links to a project folder's pages or media would be rewritten.
+pub(crate) fn preview_folder_rewrites(
+    studio_path: &Path,
+    old_folder: &str,
+    new_folder: &str,
+) -> Result<Vec<LinkRewrite>, String> {
+    let urls = folder_urls(old_folder, new_folder);
+    let pairs: Vec<(&str, &str)> = urls.iter().map(|(a, b)| (a.as_str(), b.as_str())).collect();
+    let pending = collect_rewrites(studio_path, &pairs)?;
+    Ok(to_report(studio_path, &pending))
+}
+
+/// Points links to a project folder's pages and media at its new name.
+pub(crate) fn apply_folder_rewrites(
+    studio_path: &Path,
+    old_folder: &str,
+    new_folder: &str,
+) -> Result<Vec<LinkRewrite>, String> {
+    let urls = folder_urls(old_folder, new_folder);
+    let pairs: Vec<(&str, &str)> = urls.iter().map(|(a, b)| (a.as_str(), b.as_str())).collect();
+    let pending = collect_rewrites(studio_path, &pairs)?;
+
+    for rewrite in &pending {
+        crate::write_file_atomic(&rewrite.path, &rewrite.content)?;
+    }
+
+    Ok(to_report(studio_path, &pending))
+}
+
+/// Rewrites every internal link to `old_url` (or anything below it) so it points at `new_url`.
+pub(crate) fn apply_link_rewrites(
+    studio_path: &Path,
+    old_url: &str,
+    new_url: &str,
+) -> Result<Vec<LinkRewrite>, String> {
+    let pending = collect_rewrites(studio_path, &[(old_url, new_url)])?;
+
+    for rewrite in &pending {
+        crate::write_file_atomic(&rewrite.path, &rewrite.content)?;
+    }
+
+    Ok(to_report(studio_path, &pending))
+}
+
+/// Rewrites links to `old_url` in the files below `dir` only, e.g. a copied project.
+pub(crate) fn apply_link_rewrites_in(
+    dir: &Path,
+    old_url: &str,
+    new_url: &str,
+) -> Result<(), String> {
+    for rewrite in collect_rewrites_in(dir, &[(old_url, new_url)])? {
+        crate::write_file_atomic(&rewrite.path, &rewrite.content)?;
+    }
+    Ok(())
+}
+
+fn to_report(studio_path: &Path, pending: &[PendingRewrite]) -> Vec<LinkRewrite> {
+    pending
+        .iter()
+        .map(|rewrite| LinkRewrite {
+            path: rewrite
+                .path
+                .strip_prefix(studio_path)
+                .unwrap_or(&rewrite.path)
+                .to_string_lossy()
+                .to_string(),
+            occurrences: rewrite.occurrences,
+        })
+        .collect()
+}
+
+fn collect_rewrites(
+    studio_path: &Path,
+    urls: &[(&str, &str)],
+) -> Result<Vec<PendingRewrite>, String> {
+    collect_rewrites_in(&studio_path.join("src"), urls)
+}
+
+fn collect_rewrites_in(dir: &Path, urls: &[(&str, &str)]) -> Result<Vec<PendingRewrite>, String> {
+    let mut files = Vec::new();
+    collect_content_files(dir, &mut files)?;
+    files.sort();
+
+    let mut pending = Vec::new();
+    for path in files {
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut updated = content;
+        let mut occurrences = 0;
+        for (old_url, new_url) in urls {
+            let (rewritten, count) = rewrite_links(&updated, old_url, new_url);
+            updated = rewritten;
+            occurrences += count;
+        }
+        if occurrences > 0 {
+            pending.push(PendingRewrite {
+                path,
+                content: updated,
+                occurrences,
+            });
+        }
+    }
+
+    Ok(pending)
+}
+
+fn collect_content_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_content_files(&path, files)?;
+        } else if matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("md") | Some("mdx") | Some("astro")
+        ) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces `old_url` with `new_url` wherever it starts a link target, returning the
+/// updated content and the number of replacements. `/old` matches `/old`, `/old/x`,
+/// `/old#x` and `/old?x`, but never `/older`.
+pub(crate) fn rewrite_links(content: &str, old_url: &str, new_url: &str) -> (String, usize) {
+    if old_url.is_empty() || old_url == new_url {
+        return (content.to_string(), 0);
+    }
+
+    let mut result = String::with_capacity(content.len());
+    let mut occurrences = 0;
+    let mut last = 0;
+
+    for (start, _) in content.match_indices(old_url) {
+        if start < last {
+            continue;
+        }
+
+        let before = &content[..start];
+        let after = &content[start + old_url.len()..];
+        let in_link = LINK_PREFIXES.iter().any(|prefix| before.ends_with(prefix));
+        let at_boundary = match after.chars().next() {
+            None => true,
+            Some(c) => matches!(
+                c,
+                '/' | ')' | '#' | '?' | '"' | '\'' | '>' | ' ' | '\n' | '\r' | '\t'
+            ),
+        };
+
+        if in_link && at_boundary {
+            result.push_str(&content[last..start]);
+            result.push_str(new_url);
+            last = start + old_url.len();
+            occurrences += 1;
+        }
+    }
+
+    result.push_str(&content[last..]);
+    (result, occurrences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rewrite_links;
+
+    #[test]
+    fn leaves_closing_tags_alone() {
+        let content = "<section>\n[Intro](/section/intro)\n</section>\n";
+        let (updated, occurrences) = rewrite_links(content, "/section", "/chapters");
+        assert_eq!(updated, "<section>\n[Intro](/chapters/intro)\n</section>\n");
+        assert_eq!(occurrences, 1);
+    }
+
+    #[test]
+    fn matches_whole_path_segments_only() {
+        let content = "[a](/foo) [b](/foo-bar/x) [c](/foo/x) [d](/foo#top) [e](/foo?page=2)";
+        let (updated, occurrences) = rewrite_links(content, "/foo", "/baz");
+        assert_eq!(
+            updated,
+            "[a](/baz) [b](/foo-bar/x) [c](/baz/x) [d](/baz#top) [e](/baz?page=2)"
+        );
+        assert_eq!(occurrences, 4);
+    }
+
+    #[test]
+    fn rewrites_reference_links() {
+        let content = "See [the guide][guide].\n\n[guide]: /docs/guide\n";
+        let (updated, occurrences) = rewrite_links(content, "/docs", "/manual");
+        assert_eq!(
+            updated,
+            "See [the guide][guide].\n\n[guide]: /manual/guide\n"
+        );
+        assert_eq!(occurrences, 1);
+    }
+
+    #[test]
+    fn rewrites_html_attributes() {
+        let content =
+            "<img src=\"/media/docs/a.png\"> <img src='/media/docs/b.png'> <a href=\"/docs\">";
+        let (updated, occurrences) = rewrite_links(content, "/media/docs", "/media/manual");
+        assert_eq!(
+            updated,
+            "<img src=\"/media/manual/a.png\"> <img src='/media/manual/b.png'> <a href=\"/docs\">"
+        );
+        assert_eq!(occurrences, 2);
+    }
+
+    #[test]
+    fn ignores_plain_text_mentions() {
+        let content = "The /docs folder holds the docs.";
+        assert_eq!(rewrite_links(content, "/docs", "/manual").1, 0);
+    }
+}
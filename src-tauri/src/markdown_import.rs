@@ -0,0 +1,570 @@
+use crate::frontmatter;
+use crate::sitemap::encode_path;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportedNote {
+    source: String, // Path relative to the source folder
+    title: String,
+    slug: String,
+    attachments: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportIssue {
+    source: String,
+    detail: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MarkdownImportReport {
+    dry_run: bool,
+    notes: Vec<ImportedNote>,
+    unresolved_links: Vec<ImportIssue>,
+    failures: Vec<ImportIssue>,
+}
+
+struct PlannedNote {
+    source: PathBuf,
+    relative: String,
+    slug: String,
+}
+
+/// Walks the source folder, skipping hidden folders such as `.obsidian` and `.git`.
+fn collect_source_files(
+    dir: &Path,
+    notes: &mut Vec<PathBuf>,
+    attachments: &mut Vec<PathBuf>,
+) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+
+        if path.is_dir() {
+            if !hidden {
+                collect_source_files(&path, notes, attachments)?;
+            }
+        } else if path.is_file() && !hidden {
+            if path.extension().is_some_and(|ext| ext == "md") {
+                notes.push(path);
+            } else {
+                attachments.push(path);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn file_key(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+fn note_key(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Resolves links and embeds against the other notes and attachments in the source folder.
+struct LinkResolver<'a> {
+    project_name: &'a str,
+    source_path: &'a Path,
+    slugs_by_note: HashMap<String, String>,
+    attachments_by_name: HashMap<String, PathBuf>,
+}
+
+impl LinkResolver<'_> {
+    fn note_url(&self, target: &str) -> Option<String> {
+        let name = target.rsplit('/').next().unwrap_or(target);
+        let name = name.strip_suffix(".md").unwrap_or(name).to_lowercase();
+        self.slugs_by_note
+            .get(&name)
+            .map(|slug| format!("/{}/{}", self.project_name, slug))
+    }
+
+    fn attachment(&self, note_dir: &Path, target: &str) -> Option<PathBuf> {
+        // Resolved without the disk so `..` can't leave the source folder
+        let mut relative = note_dir.to_path_buf();
+        for component in Path::new(target).components() {
+            match component {
+                Component::ParentDir => {
+                    relative.pop();
+                }
+                Component::Normal(part) => relative.push(part),
+                _ => {}
+            }
+        }
+        if relative.starts_with(self.source_path) && relative.is_file() {
+            return Some(relative);
+        }
+        let name = target.rsplit('/').next().unwrap_or(target).to_lowercase();
+        self.attachments_by_name.get(&name).cloned()
+    }
+
+    /// Where an attachment goes below the post's assets folder: its path in
+    /// the source folder, so files of the same name in different folders
+    /// stay apart.
+    fn attachment_path(&self, attachment: &Path) -> String {
+        attachment
+            .strip_prefix(self.source_path)
+            .unwrap_or(attachment)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Rewrites `[[wikilinks]]`, `![[embeds]]` and relative markdown links. Returns the
+/// new note, the attachments it references and any links that could not be
+/// resolved. The frontmatter and code are left as they are.
+fn convert_links(
+    content: &str,
+    note_dir: &Path,
+    resolver: &LinkResolver,
+    assets_url: &str,
+) -> (String, Vec<PathBuf>, Vec<String>) {
+    let mut attachments: Vec<PathBuf> = Vec::new();
+    let mut unresolved = Vec::new();
+
+    let body = frontmatter::split(content).map_or(content, |(_, body)| body);
+    let mut converted = content[..content.len() - body.len()].to_string();
+    converted.push_str(&crate::post_format::map_prose(body, |text| {
+        convert_prose_links(
+            text,
+            note_dir,
+            resolver,
+            assets_url,
+            &mut attachments,
+            &mut unresolved,
+        )
+    }));
+
+    (converted, attachments, unresolved)
+}
+
+fn convert_prose_links(
+    text: &str,
+    note_dir: &Path,
+    resolver: &LinkResolver,
+    assets_url: &str,
+    attachments: &mut Vec<PathBuf>,
+    unresolved: &mut Vec<String>,
+) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("[[") {
+        let Some(end) = rest[start..].find("]]").map(|i| start + i) else {
+            break;
+        };
+
+        let embed = rest[..start].ends_with('!');
+        let inner = &rest[start + 2..end];
+        let (target, alias) = match inner.split_once('|') {
+            Some((target, alias)) => (target.trim(), Some(alias.trim())),
+            None => (inner.trim(), None),
+        };
+        let (target, heading) = match target.split_once('#') {
+            Some((target, heading)) => (target, Some(heading)),
+            None => (target, None),
+        };
+
+        result.push_str(&rest[..if embed { start - 1 } else { start }]);
+
+        if embed {
+            match resolver.attachment(note_dir, target) {
+                Some(path) => {
+                    result.push_str(&format!(
+                        "![{}]({}/{})",
+                        alias.unwrap_or(target),
+                        assets_url,
+                        encode_path(&resolver.attachment_path(&path))
+                    ));
+                    if !attachments.contains(&path) {
+                        attachments.push(path);
+                    }
+                }
+                // Embedded notes become plain links
+                None => match resolver.note_url(target) {
+                    Some(url) => {
+                        result.push_str(&format!("[{}]({})", alias.unwrap_or(target), url))
+                    }
+                    None => {
+                        unresolved.push(inner.to_string());
+                        result.push_str(&rest[start - 1..end + 2]);
+                    }
+                },
+            }
+        } else {
+            let text = alias.unwrap_or(if target.is_empty() {
+                heading.unwrap_or_default()
+            } else {
+                target
+            });
+            let anchor = heading
+                .map(|h| format!("#{}", crate::sanitize_slug(h)))
+                .unwrap_or_default();
+            let url = if target.is_empty() {
+                Some(String::new())
+            } else {
+                resolver.note_url(target)
+            };
+
+            match url {
+                Some(url) => result.push_str(&format!("[{}]({}{})", text, url, anchor)),
+                None => {
+                    // Keep the text readable even when the note was not imported
+                    unresolved.push(inner.to_string());
+                    result.push_str(text);
+                }
+            }
+        }
+
+        rest = &rest[end + 2..];
+    }
+    result.push_str(rest);
+
+    // Relative markdown links: `[text](Other Note.md)` and `![](images/photo.png)`
+    let mut converted = String::with_capacity(result.len());
+    let mut rest = result.as_str();
+    while let Some(start) = rest.find("](") {
+        let Some(end) = rest[start + 2..].find(')').map(|i| start + 2 + i) else {
+            break;
+        };
+        let target = rest[start + 2..end].replace("%20", " ");
+        converted.push_str(&rest[..start + 2]);
+
+        let is_relative = !target.is_empty()
+            && !target.starts_with('/')
+            && !target.starts_with('#')
+            && !target.contains("://")
+            && !target.starts_with("mailto:");
+
+        if !is_relative {
+            converted.push_str(&rest[start + 2..end]);
+        } else if target.ends_with(".md") {
+            match resolver.note_url(&target) {
+                Some(url) => converted.push_str(&url),
+                None => {
+                    unresolved.push(target.clone());
+                    converted.push_str(&rest[start + 2..end]);
+                }
+            }
+        } else {
+            match resolver.attachment(note_dir, &target) {
+                Some(path) => {
+                    converted.push_str(&format!(
+                        "{}/{}",
+                        assets_url,
+                        encode_path(&resolver.attachment_path(&path))
+                    ));
+                    if !attachments.contains(&path) {
+                        attachments.push(path);
+                    }
+                }
+                None => {
+                    unresolved.push(target.clone());
+                    converted.push_str(&rest[start + 2..end]);
+                }
+            }
+        }
+
+        converted.push(')');
+        rest = &rest[end + 1..];
+    }
+    converted.push_str(rest);
+    converted
+}
+
+/// Adds the frontmatter studio posts expect without disturbing fields the note already has.
+fn normalize_frontmatter(content: &str, title: &str, source: &Path) -> String {
+    let mut content = content.to_string();
+
+    if frontmatter::get_field(&content, "title").is_none() {
+        content = frontmatter::set_field(&content, "title", &frontmatter::quote(title));
+    }
+    if frontmatter::get_field(&content, "date").is_none() {
+        let date = fs::metadata(source)
+            .and_then(|metadata| metadata.modified())
+            .map(chrono::DateTime::<chrono::Local>::from)
+            .unwrap_or_else(|_| chrono::Local::now());
+        content = frontmatter::set_field(&content, "date", &date.format("%Y-%m-%d").to_string());
+    }
+    if frontmatter::get_field(&content, "status").is_none() {
+        // Imported notes are reviewed before they go live
+        content = frontmatter::set_field(&content, "status", "draft");
+    }
+
+    content
+}
+
+#[tauri::command]
+pub async fn import_markdown_folder(
+    project_name: String,
+    source_dir: String,
+    dry_run: Option<bool>,
+) -> Result<MarkdownImportReport, String> {
+    let studio_path = crate::studio_dir()?;
+    let project_path = crate::sections::resolve_project_path(&studio_path, &project_name)?;
+    let source_path = PathBuf::from(&source_dir);
+    let dry_run = dry_run.unwrap_or(false);
+
+    if !source_path.is_dir() {
+        return Err(format!("{} is not a folder", source_path.display()));
+    }
+
+    let mut note_paths = Vec::new();
+    let mut attachment_paths = Vec::new();
+    collect_source_files(&source_path, &mut note_paths, &mut attachment_paths)?;
+    note_paths.sort();
+
+    // Pick every slug up front so links between imported notes can be resolved
+    let mut taken: HashSet<String> = HashSet::new();
+    let mut planned = Vec::new();
+    for source in note_paths {
+        let stem = source
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // Note names are titles; ones that can't be slugs import as untitled
+        let base_slug = crate::validation::validate(
+            crate::validation::NameKind::Post,
+            &project_path,
+            &stem,
+            None,
+        )
+        .allow_collision()
+        .into_result()
+        .map(|(_, slug)| slug)
+        .unwrap_or_else(|_| "untitled".to_string());
+
+        let mut slug = base_slug.clone();
+        let mut counter = 1;
+        while taken.contains(&slug) || crate::post_format::post_exists(&project_path, &slug) {
+            slug = format!("{}-{}", base_slug, counter);
+            counter += 1;
+        }
+        taken.insert(slug.clone());
+
+        let relative = source
+            .strip_prefix(&source_path)
+            .unwrap_or(&source)
+            .to_string_lossy()
+            .to_string();
+        planned.push(PlannedNote {
+            source,
+            relative,
+            slug,
+        });
+    }
+
+    let resolver = LinkResolver {
+        project_name: &project_name,
+        source_path: &source_path,
+        slugs_by_note: planned
+            .iter()
+            .map(|note| (note_key(&note.source), note.slug.clone()))
+            .collect(),
+        attachments_by_name: attachment_paths
+            .into_iter()
+            .map(|path| (file_key(&path), path))
+            .collect(),
+    };
+
+    let mut report = MarkdownImportReport {
+        dry_run,
+        ..Default::default()
+    };
+
+    for note in &planned {
+        let content = match fs::read_to_string(&note.source) {
+            Ok(content) => content,
+            Err(e) => {
+                report.failures.push(ImportIssue {
+                    source: note.relative.clone(),
+                    detail: format!("Failed to read: {}", e),
+                });
+                continue;
+            }
+        };
+
+        let stem = note
+            .source
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy();
+        let title = crate::extract_title_from_markdown(&content, &stem);
+        let note_dir = note.source.parent().unwrap_or(&source_path);
+        let assets_url = format!("/media/{}/{}", project_name, note.slug);
+
+        let (converted, attachments, unresolved) =
+            convert_links(&content, note_dir, &resolver, &assets_url);
+        let converted = normalize_frontmatter(&converted, &title, &note.source);
+
+        report
+            .unresolved_links
+            .extend(unresolved.into_iter().map(|link| ImportIssue {
+                source: note.relative.clone(),
+                detail: link,
+            }));
+
+        if !dry_run {
+            let assets_dir = crate::post_assets_dir(&studio_path, &project_name, &note.slug);
+            let written = crate::write_file_atomic(
+                &project_path.join(format!("{}.md", note.slug)),
+                &converted,
+            )
+            .and_then(|_| {
+                for attachment in &attachments {
+                    let target = assets_dir.join(resolver.attachment_path(attachment));
+                    fs::create_dir_all(target.parent().unwrap_or(&assets_dir))
+                        .and_then(|_| fs::copy(attachment, &target))
+                        .map_err(|e| format!("Failed to copy {}: {}", attachment.display(), e))?;
+                }
+                Ok(())
+            });
+
+            if let Err(detail) = written {
+                report.failures.push(ImportIssue {
+                    source: note.relative.clone(),
+                    detail,
+                });
+                continue;
+            }
+        }
+
+        report.notes.push(ImportedNote {
+            source: note.relative.clone(),
+            title,
+            slug: note.slug.clone(),
+            attachments: attachments
+                .iter()
+                .map(|path| resolver.attachment_path(path))
+                .collect(),
+        });
+    }
+
+    if !dry_run {
+        crate::refresh_site_files(&studio_path)?;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A source folder with two notes and two attachments sharing a name,
+    /// and a file next to it that notes must not reach.
+    fn fixture(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("studio-notes-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let source = root.join("vault");
+        for (relative, content) in [
+            ("Other Note.md", "Other"),
+            ("trips/Paris.md", "Paris"),
+            ("trips/image.png", "trip"),
+            ("work/image.png", "work"),
+            ("../outside.png", "outside"),
+        ] {
+            fs::create_dir_all(source.join(relative).parent().unwrap()).unwrap();
+            fs::write(source.join(relative), content).unwrap();
+        }
+        source
+    }
+
+    fn convert(source: &Path, note_dir: &str, content: &str) -> (String, Vec<String>, Vec<String>) {
+        let resolver = LinkResolver {
+            project_name: "notes",
+            source_path: source,
+            slugs_by_note: HashMap::from([
+                ("other note".to_string(), "other-note".to_string()),
+                ("paris".to_string(), "paris".to_string()),
+            ]),
+            attachments_by_name: HashMap::from([(
+                "image.png".to_string(),
+                source.join("work/image.png"),
+            )]),
+        };
+        let (converted, attachments, unresolved) = convert_links(
+            content,
+            &source.join(note_dir),
+            &resolver,
+            "/media/notes/post",
+        );
+        let attachments = attachments
+            .iter()
+            .map(|path| resolver.attachment_path(path))
+            .collect();
+        (converted, attachments, unresolved)
+    }
+
+    #[test]
+    fn converts_aliases_and_headings() {
+        let source = fixture("links");
+        let (converted, _, unresolved) = convert(
+            &source,
+            "",
+            "See [[Other Note|the other one]], [[Paris#Day One]] and [[#Intro]].\n",
+        );
+        assert_eq!(
+            converted,
+            "See [the other one](/notes/other-note), [Paris](/notes/paris#day-one) and [Intro](#intro).\n"
+        );
+        assert!(unresolved.is_empty());
+        fs::remove_dir_all(source.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn embeds_keep_their_folder_so_names_stay_apart() {
+        let source = fixture("embeds");
+        let (converted, attachments, _) = convert(
+            &source,
+            "trips",
+            "![[image.png]]\n\n![Work](../work/image.png)\n\n![Out](../../outside.png)\n",
+        );
+        assert_eq!(
+            converted,
+            "![image.png](/media/notes/post/trips/image.png)\n\n![Work](/media/notes/post/work/image.png)\n\n![Out](../../outside.png)\n"
+        );
+        assert_eq!(attachments, ["trips/image.png", "work/image.png"]);
+        fs::remove_dir_all(source.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn reports_unresolved_links_and_keeps_their_text() {
+        let source = fixture("unresolved");
+        let (converted, _, unresolved) = convert(
+            &source,
+            "",
+            "A [[Missing Note|missing]] link and [gone](Gone.md).\n",
+        );
+        assert_eq!(converted, "A missing link and [gone](Gone.md).\n");
+        assert_eq!(unresolved, ["Missing Note|missing", "Gone.md"]);
+        fs::remove_dir_all(source.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn leaves_code_and_frontmatter_alone() {
+        let source = fixture("code");
+        let content = "---\ntitle: \"[[Paris]]\"\n---\n```\n[[Paris]] and [x](Other Note.md)\n```\n\nInline `[[Paris]]` but [[Paris]].\n";
+        let (converted, _, unresolved) = convert(&source, "", content);
+        assert_eq!(
+            converted,
+            "---\ntitle: \"[[Paris]]\"\n---\n```\n[[Paris]] and [x](Other Note.md)\n```\n\nInline `[[Paris]]` but [Paris](/notes/paris).\n"
+        );
+        assert!(unresolved.is_empty());
+        fs::remove_dir_all(source.parent().unwrap()).unwrap();
+    }
+}
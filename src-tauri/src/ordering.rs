@@ -0,0 +1,158 @@
+use crate::project_manifest;
+use crate::site_config;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+// The line write_project_scaffold emits into index.astro; rewritten on reorder
+const POST_ORDER_DECLARATION: &str = "const postOrder = ";
+
+/// Position of `name` in a stored order. Names missing from the order sort
+/// after every listed one, so new posts and projects land at the end.
+pub(crate) fn order_position(order: &[String], name: &str) -> usize {
+    order
+        .iter()
+        .position(|entry| entry == name)
+        .unwrap_or(order.len())
+}
+
+/// The `const postOrder = [...]` line for a project's index.astro.
+pub(crate) fn post_order_declaration(order: &[String]) -> String {
+    format!(
+        "{}{};",
+        POST_ORDER_DECLARATION,
+        serde_json::to_string(order).unwrap_or_else(|_| "[]".to_string())
+    )
+}
+
+/// Updates the post order emitted into the project's index.astro. Index pages
+/// scaffolded before ordering existed have no such line and are left as is.
+fn write_index_post_order(project_path: &Path, order: &[String]) -> Result<(), String> {
+    let index_path = project_path.join("index.astro");
+    let Ok(content) = fs::read_to_string(&index_path) else {
+        return Ok(());
+    };
+    if !content
+        .lines()
+        .any(|line| line.starts_with(POST_ORDER_DECLARATION))
+    {
+        return Ok(());
+    }
+
+    let updated: Vec<String> = content
+        .lines()
+        .map(|line| {
+            if line.starts_with(POST_ORDER_DECLARATION) {
+                post_order_declaration(order)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    let mut updated = updated.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+
+    crate::write_file_atomic(&index_path, &updated)
+}
+
+fn save_post_order(project_path: &Path, order: Vec<String>) -> Result<(), String> {
+    let mut manifest = project_manifest::load_manifest(project_path)?;
+    manifest.post_order = order;
+    project_manifest::save_manifest(project_path, &manifest)?;
+    write_index_post_order(project_path, &manifest.post_order)
+}
+
+/// Keeps a renamed post in its place, or drops it from the order when it
+/// leaves the project (`new_slug` is `None`).
+pub(crate) fn rename_in_post_order(
+    project_path: &Path,
+    old_slug: &str,
+    new_slug: Option<&str>,
+) -> Result<(), String> {
+    let order = project_manifest::load_manifest(project_path)?.post_order;
+    if !order.iter().any(|slug| slug == old_slug) {
+        return Ok(());
+    }
+
+    let order = order
+        .into_iter()
+        .filter_map(|slug| match (slug == old_slug, new_slug) {
+            (true, Some(new_slug)) => Some(new_slug.to_string()),
+            (true, None) => None,
+            (false, _) => Some(slug),
+        })
+        .collect();
+    save_post_order(project_path, order)
+}
+
+/// Keeps a renamed project folder in its place in the workspace order.
+pub(crate) fn rename_in_project_order(
+    studio_path: &Path,
+    old_folder: &str,
+    new_folder: &str,
+) -> Result<(), String> {
+    let mut config = site_config::load_site_config(studio_path)?;
+    let Some(entry) = config
+        .project_order
+        .iter_mut()
+        .find(|folder| *folder == old_folder)
+    else {
+        return Ok(());
+    };
+
+    *entry = new_folder.to_string();
+    site_config::save_site_config(studio_path, &config)
+}
+
+fn check_unique(names: &[String]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    match names.iter().find(|name| !seen.insert(name.as_str())) {
+        Some(duplicate) => Err(format!("'{}' appears more than once", duplicate)),
+        None => Ok(()),
+    }
+}
+
+/// Stores the order of a project's posts. Posts left out of `slugs` follow
+/// the listed ones in filename order.
+#[tauri::command]
+pub async fn reorder_posts(project_name: String, slugs: Vec<String>) -> Result<(), String> {
+    let studio_path = crate::studio_dir()?;
+    let project_path = crate::sections::resolve_project_path(&studio_path, &project_name)?;
+    check_unique(&slugs)?;
+    if let Some(missing) = slugs
+        .iter()
+        .find(|slug| !crate::post_format::post_exists(&project_path, slug))
+    {
+        return Err(format!("Post '{}' does not exist", missing));
+    }
+
+    save_post_order(&project_path, slugs)
+}
+
+/// Stores the order of the workspace's projects. Projects left out of
+/// `folder_names` follow the listed ones by display name.
+#[tauri::command]
+pub async fn reorder_projects(folder_names: Vec<String>) -> Result<(), String> {
+    let studio_path = crate::studio_dir()?;
+    let pages_path = studio_path.join("src").join("pages");
+
+    check_unique(&folder_names)?;
+    for folder in &folder_names {
+        crate::sections::resolve_project_path(&studio_path, folder)?;
+        if folder.contains('/') {
+            return Err(format!(
+                "'{}' is a section; order it within its project",
+                folder
+            ));
+        }
+        if crate::taxonomy::is_tag_pages_dir(&pages_path, folder) {
+            return Err(format!("'{}' holds the tag pages, not a project", folder));
+        }
+    }
+
+    let mut config = site_config::load_site_config(&studio_path)?;
+    config.project_order = folder_names;
+    site_config::save_site_config(&studio_path, &config)
+}
@@ -0,0 +1,415 @@
+use crate::frontmatter;
+use crate::post_status;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// The integration Astro needs to build .mdx pages
+const MDX_INTEGRATION: &str = "@astrojs/mdx";
+
+// HTML elements without a closing tag, which MDX requires to be self-closed
+const VOID_ELEMENTS: [&str; 6] = ["br", "hr", "img", "input", "source", "wbr"];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostFormat {
+    #[default]
+    Md,
+    Mdx,
+}
+
+impl PostFormat {
+    pub(crate) fn extension(self) -> &'static str {
+        match self {
+            PostFormat::Md => "md",
+            PostFormat::Mdx => "mdx",
+        }
+    }
+
+    pub(crate) fn filename(self, slug: &str) -> String {
+        format!("{}.{}", slug, self.extension())
+    }
+
+    fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "md" => Some(PostFormat::Md),
+            "mdx" => Some(PostFormat::Mdx),
+            _ => None,
+        }
+    }
+}
+
+/// Splits a post filename such as `hello.mdx` into its slug and format.
+pub(crate) fn parse_filename(filename: &str) -> Option<(&str, PostFormat)> {
+    let (slug, extension) = filename.rsplit_once('.')?;
+    Some((slug, PostFormat::from_extension(extension)?))
+}
+
+pub(crate) fn is_post_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(parse_filename)
+            .is_some()
+}
+
+/// The existing `.md` or `.mdx` file of a post.
+pub(crate) fn find_post_file(project_path: &Path, slug: &str) -> Option<(PathBuf, PostFormat)> {
+    // A slug names a file in the folder, never a path out of it
+    if slug.is_empty() || slug.starts_with('.') || slug.contains(['/', '\\']) {
+        return None;
+    }
+    [PostFormat::Md, PostFormat::Mdx]
+        .into_iter()
+        .map(|format| (project_path.join(format.filename(slug)), format))
+        .find(|(path, _)| path.is_file())
+}
+
+/// The post's file, or where a Markdown post with that slug would go.
+pub(crate) fn post_file_path(project_path: &Path, slug: &str) -> PathBuf {
+    find_post_file(project_path, slug)
+        .map(|(path, _)| path)
+        .unwrap_or_else(|| project_path.join(PostFormat::Md.filename(slug)))
+}
+
+/// Whether a post with this slug exists in either format. Slugs are shared
+/// between formats because both files would be served at the same URL.
+pub(crate) fn post_exists(project_path: &Path, slug: &str) -> bool {
+    find_post_file(project_path, slug).is_some()
+}
+
+/// MDX posts only build once the site has the MDX integration installed.
+pub(crate) fn ensure_mdx_supported(studio_path: &Path) -> Result<(), String> {
+    let package_json = fs::read_to_string(studio_path.join("package.json")).unwrap_or_default();
+    if package_json.contains(&format!("\"{}\"", MDX_INTEGRATION)) {
+        return Ok(());
+    }
+    Err(format!(
+        "This site can't build MDX posts yet. Add the {} integration to package.json and astro.config.mjs first",
+        MDX_INTEGRATION
+    ))
+}
+
+/// Tracks fenced code blocks as a body is read line by line.
+#[derive(Default)]
+struct Fences {
+    open: Option<String>,
+}
+
+impl Fences {
+    /// Whether the line is part of a fenced code block, fences included.
+    fn is_code(&mut self, line: &str) -> bool {
+        let trimmed = line.trim_start();
+        let fence_char = trimmed.chars().next().filter(|&c| c == '`' || c == '~');
+        let marker: String = match fence_char {
+            Some(fence_char) => trimmed.chars().take_while(|&c| c == fence_char).collect(),
+            None => String::new(),
+        };
+
+        match &self.open {
+            Some(open) => {
+                if marker.len() >= open.len()
+                    && marker.starts_with(&open[..1])
+                    && trimmed[marker.len()..].trim().is_empty()
+                {
+                    self.open = None;
+                }
+                true
+            }
+            None if marker.len() >= 3 => {
+                self.open = Some(marker);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Applies `convert` to the prose of a post body: everything outside fenced
+/// code blocks and inline code spans. Lines inside fences are kept verbatim.
+pub(crate) fn map_prose(body: &str, mut convert: impl FnMut(&str) -> String) -> String {
+    let mut fences = Fences::default();
+    let mut output = String::with_capacity(body.len());
+
+    for line in body.split_inclusive('\n') {
+        if fences.is_code(line) {
+            output.push_str(line);
+        } else {
+            output.push_str(&map_outside_code_spans(line, &mut convert));
+        }
+    }
+
+    output
+}
+
+fn map_outside_code_spans(line: &str, convert: &mut impl FnMut(&str) -> String) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(start) = rest.find('`') {
+        let run = rest[start..].chars().take_while(|&c| c == '`').count();
+        let ticks = &rest[start..start + run];
+        let after = &rest[start + run..];
+
+        // A span closes at the next run of exactly as many backticks
+        let close = after.match_indices(ticks).find(|(index, _)| {
+            !after[index + run..].starts_with('`') && !after[..*index].ends_with('`')
+        });
+        match close {
+            Some((index, _)) => {
+                output.push_str(&convert(&rest[..start]));
+                output.push_str(&rest[start..start + run + index + run]);
+                rest = &after[index + run..];
+            }
+            None => {
+                output.push_str(&convert(&rest[..start + run]));
+                rest = after;
+            }
+        }
+    }
+
+    output.push_str(&convert(rest));
+    output
+}
+
+/// Rewrites Markdown that MDX would read as JavaScript or JSX: braces, stray
+/// `<`, HTML comments, autolinks and unclosed void elements.
+fn markdown_to_mdx(body: &str) -> String {
+    let mut in_comment = false;
+
+    map_prose(body, |text| {
+        let mut output = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(c) = rest.chars().next() {
+            if in_comment {
+                if let Some(after) = rest.strip_prefix("-->") {
+                    output.push_str("*/}");
+                    in_comment = false;
+                    rest = after;
+                } else {
+                    output.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+                continue;
+            }
+
+            if let Some(after) = rest.strip_prefix("<!--") {
+                output.push_str("{/*");
+                in_comment = true;
+                rest = after;
+            } else if c == '{' || c == '}' {
+                output.push('\\');
+                output.push(c);
+                rest = &rest[1..];
+            } else if c == '<' {
+                let (converted, consumed) = convert_angle_bracket(rest);
+                output.push_str(&converted);
+                rest = &rest[consumed..];
+            } else {
+                output.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+
+        output
+    })
+}
+
+/// Converts the construct starting at a `<`, returning it and the bytes consumed.
+fn convert_angle_bracket(text: &str) -> (String, usize) {
+    let Some(end) = text.find('>') else {
+        return ("\\<".to_string(), 1);
+    };
+    let inner = &text[1..end];
+
+    // Autolinks such as <https://example.com>
+    if inner.contains("://") && !inner.contains(char::is_whitespace) {
+        return (format!("[{}]({})", inner, inner), end + 1);
+    }
+
+    let tag_name: String = inner
+        .trim_start_matches('/')
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect();
+    let starts_tag = inner
+        .trim_start_matches('/')
+        .starts_with(|c: char| c.is_ascii_alphabetic());
+    if !starts_tag {
+        return ("\\<".to_string(), 1);
+    }
+
+    let tag_name = tag_name.to_lowercase();
+    if VOID_ELEMENTS.contains(&tag_name.as_str()) && !inner.ends_with('/') {
+        return (format!("<{} />", inner.trim_end()), end + 1);
+    }
+
+    (text[..=end].to_string(), end + 1)
+}
+
+/// Splits the `import` and `export` statements off an MDX body. As in MDX, a
+/// statement starts a line outside code and runs to the next blank line, so
+/// multi-line imports are taken whole.
+fn split_mdx_statements(body: &str) -> (Vec<String>, String) {
+    let mut statements: Vec<String> = Vec::new();
+    let mut rest = String::with_capacity(body.len());
+    let mut fences = Fences::default();
+    let mut in_statement = false;
+
+    for line in body.split_inclusive('\n') {
+        if in_statement {
+            if line.trim().is_empty() {
+                in_statement = false;
+                rest.push_str(line);
+            } else if line.starts_with("import ") || line.starts_with("export ") {
+                statements.push(line.to_string());
+            } else if let Some(statement) = statements.last_mut() {
+                statement.push_str(line);
+            }
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        if !fences.is_code(line)
+            && (trimmed.starts_with("import ") || trimmed.starts_with("export "))
+        {
+            statements.push(line.to_string());
+            in_statement = true;
+        } else {
+            rest.push_str(line);
+        }
+    }
+
+    (statements, rest)
+}
+
+/// Drops the `import` and `export` statements of an MDX body, for output such
+/// as feeds and ebooks that render the Markdown without the site's components.
+pub(crate) fn strip_mdx_statements(body: &str) -> String {
+    split_mdx_statements(body).1
+}
+
+/// What keeps an MDX post from being plain Markdown, if anything.
+fn mdx_only_syntax(body: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut note = |item: String| {
+        if !found.contains(&item) {
+            found.push(item);
+        }
+    };
+
+    let (statements, prose) = split_mdx_statements(body);
+    for statement in statements {
+        // Listed on one line, however the statement was wrapped
+        let statement: Vec<&str> = statement.split_whitespace().collect();
+        note(format!("`{}`", statement.join(" ")));
+    }
+
+    map_prose(&prose, |text| {
+        let mut rest = text;
+        while let Some(index) = rest.find('<') {
+            let after = &rest[index + 1..];
+            if after.starts_with(|c: char| c.is_ascii_uppercase()) {
+                let name: String = after
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric() || *c == '.')
+                    .collect();
+                note(format!("the <{}> component", name));
+            }
+            rest = after;
+        }
+
+        // Escaped braces and comments convert back; anything else is an expression
+        let unescaped = text
+            .replace("\\{", "")
+            .replace("{/*", "")
+            .replace("\\}", "")
+            .replace("*/}", "");
+        if unescaped.contains('{') {
+            note("JavaScript expressions".to_string());
+        }
+        text.to_string()
+    });
+
+    found
+}
+
+/// Undoes the escapes `markdown_to_mdx` adds.
+fn mdx_to_markdown(body: &str) -> String {
+    map_prose(body, |text| {
+        text.replace("{/*", "<!--")
+            .replace("*/}", "-->")
+            .replace("\\{", "{")
+            .replace("\\}", "}")
+            .replace("\\<", "<")
+    })
+}
+
+/// Converts a post between Markdown and MDX, keeping its slug and URL.
+/// MDX posts that import or use components can't become Markdown until those are removed.
+#[tauri::command]
+pub async fn convert_post(
+    project_name: String,
+    slug: String,
+    format: PostFormat,
+) -> Result<crate::Post, String> {
+    let studio_path = crate::studio_dir()?;
+    let project_path = crate::sections::resolve_project_path(&studio_path, &project_name)?;
+
+    let (old_path, current) = find_post_file(&project_path, &slug)
+        .ok_or_else(|| format!("Post '{}' does not exist", slug))?;
+    if current == format {
+        return Err(format!(
+            "Post '{}' is already .{}",
+            slug,
+            format.extension()
+        ));
+    }
+
+    let content =
+        fs::read_to_string(&old_path).map_err(|e| format!("Failed to read post file: {}", e))?;
+    let (head, body) = match frontmatter::split(&content) {
+        Some((_, body)) => content.split_at(content.len() - body.len()),
+        None => ("", content.as_str()),
+    };
+
+    let body = match format {
+        PostFormat::Mdx => {
+            ensure_mdx_supported(&studio_path)?;
+            markdown_to_mdx(body)
+        }
+        PostFormat::Md => {
+            let blockers = mdx_only_syntax(body);
+            if !blockers.is_empty() {
+                return Err(format!(
+                    "Post '{}' uses MDX features Markdown doesn't support: {}",
+                    slug,
+                    blockers.join(", ")
+                ));
+            }
+            mdx_to_markdown(body)
+        }
+    };
+    let content = format!("{}{}", head, body);
+
+    // Write the converted post in full before removing the old file, so an
+    // interrupted conversion leaves a duplicate rather than losing the post
+    let filename = format.filename(&slug);
+    crate::write_file_atomic(&project_path.join(&filename), &content)?;
+    fs::remove_file(&old_path).map_err(|e| format!("Failed to remove old post file: {}", e))?;
+    crate::refresh_site_files(&studio_path)?;
+
+    let state = post_status::read_post_state(&content);
+
+    Ok(crate::Post {
+        filename,
+        title: crate::extract_title_from_markdown(&content, &slug),
+        slug,
+        content,
+        status: state.status,
+        publish_at: state.publish_at,
+        format,
+    })
+}
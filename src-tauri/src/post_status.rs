@@ -0,0 +1,177 @@
+use crate::frontmatter;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostStatus {
+    Draft,
+    InReview,
+    Scheduled,
+    Published,
+}
+
+impl PostStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            PostStatus::Draft => "draft",
+            PostStatus::InReview => "in_review",
+            PostStatus::Scheduled => "scheduled",
+            PostStatus::Published => "published",
+        }
+    }
+
+    fn parse(value: &str) -> Option<PostStatus> {
+        match value {
+            "draft" => Some(PostStatus::Draft),
+            "in_review" => Some(PostStatus::InReview),
+            "scheduled" => Some(PostStatus::Scheduled),
+            "published" => Some(PostStatus::Published),
+            _ => None,
+        }
+    }
+}
+
+/// Status and publish time as stored in a post's frontmatter.
+pub(crate) struct PostState {
+    pub status: PostStatus,
+    pub publish_at: Option<String>,
+}
+
+/// Reads the post's status. Posts written before statuses existed have no
+/// `status` field and count as published; scheduled posts whose `publishAt`
+/// has passed are reported as published too.
+pub(crate) fn read_post_state(content: &str) -> PostState {
+    let status = frontmatter::get_field(content, "status")
+        .and_then(|value| PostStatus::parse(&value))
+        .unwrap_or(PostStatus::Published);
+    let publish_at = frontmatter::get_field(content, "publishAt");
+
+    let status = match (status, publish_at.as_deref().and_then(parse_publish_at)) {
+        (PostStatus::Scheduled, Some(at)) if at <= Utc::now() => PostStatus::Published,
+        (status, _) => status,
+    };
+
+    PostState { status, publish_at }
+}
+
+/// Accepts RFC 3339 timestamps, `YYYY-MM-DDTHH:MM` (UTC) and plain dates (midnight UTC).
+pub(crate) fn parse_publish_at(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(at.with_timezone(&Utc));
+    }
+    if let Ok(at) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M") {
+        return Some(at.and_utc());
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|at| at.and_utc())
+}
+
+fn apply_status(content: &str, status: PostStatus, publish_at: Option<&str>) -> String {
+    let content = frontmatter::set_field(content, "status", status.as_str());
+    match publish_at {
+        Some(at) => frontmatter::set_field(&content, "publishAt", &frontmatter::quote(at)),
+        None => frontmatter::remove_field(&content, "publishAt"),
+    }
+}
+
+#[tauri::command]
+pub async fn set_post_status(
+    project_name: String,
+    slug: String,
+    status: PostStatus,
+    publish_at: Option<String>,
+) -> Result<(), String> {
+    let project_path = crate::sections::resolve_project_path(&crate::studio_dir()?, &project_name)?;
+    let (file_path, _) = crate::post_format::find_post_file(&project_path, &slug)
+        .ok_or_else(|| format!("Post '{}' does not exist", slug))?;
+
+    let publish_at = match status {
+        PostStatus::Scheduled => {
+            let value = publish_at
+                .map(|at| at.trim().to_string())
+                .ok_or("Scheduled posts need a publish date")?;
+            let at = parse_publish_at(&value)
+                .ok_or_else(|| format!("'{}' is not a valid publish date", value))?;
+            Some(at.to_rfc3339())
+        }
+        _ => None,
+    };
+
+    let content =
+        fs::read_to_string(&file_path).map_err(|e| format!("Failed to read post file: {}", e))?;
+    let updated = apply_status(&content, status, publish_at.as_deref());
+
+    crate::write_file_atomic(&file_path, &updated)?;
+    crate::refresh_site_files(&crate::studio_dir()?)?;
+
+    Ok(())
+}
+
+/// Marks every scheduled post in projects and their sections whose `publishAt`
+/// has passed as published. The site's build runs the same step.
+pub(crate) fn publish_due_posts(studio_path: &Path) -> Result<Vec<String>, String> {
+    let pages_path = studio_path.join("src").join("pages");
+    let mut published = Vec::new();
+
+    if !pages_path.exists() {
+        return Ok(published);
+    }
+
+    let entries =
+        fs::read_dir(&pages_path).map_err(|e| format!("Failed to read pages directory: {}", e))?;
+    let projects: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| !name.starts_with('.') && !name.starts_with('_'))
+        .collect();
+
+    for project in projects {
+        for folder_name in crate::sections::section_paths(studio_path, &project)? {
+            let entries = fs::read_dir(pages_path.join(&folder_name))
+                .map_err(|e| format!("Failed to read project directory: {}", e))?;
+
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !crate::post_format::is_post_file(&path) {
+                    continue;
+                }
+
+                let content = fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read post file: {}", e))?;
+                let stored = frontmatter::get_field(&content, "status");
+
+                if stored.as_deref() == Some("scheduled")
+                    && read_post_state(&content).status == PostStatus::Published
+                {
+                    let publish_at = frontmatter::get_field(&content, "publishAt");
+                    let updated =
+                        apply_status(&content, PostStatus::Published, publish_at.as_deref());
+                    crate::write_file_atomic(&path, &updated)?;
+                    published.push(
+                        path.strip_prefix(&pages_path)
+                            .unwrap_or(&path)
+                            .to_string_lossy()
+                            .to_string(),
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(published)
+}
+
+#[tauri::command]
+pub async fn publish_scheduled_posts() -> Result<Vec<String>, String> {
+    let studio_path = crate::studio_dir()?;
+    let published = publish_due_posts(&studio_path)?;
+    crate::refresh_site_files(&studio_path)?;
+
+    Ok(published)
+}
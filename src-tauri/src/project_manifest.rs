@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Lives next to index.astro; the leading underscore keeps Astro from routing it
+pub(crate) const MANIFEST_FILE: &str = "_project.json";
+
+/// What feed entries carry for each post.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedContent {
+    Full,
+    #[default]
+    Summary,
+}
+
+/// Studio metadata for a project that has no place in the generated Astro files.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ProjectManifest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>, // Display name as last written to index.astro, for repair_project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover: Option<String>, // Site path of the cover image, e.g. /media/essays/cover.jpg
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feed_content: Option<FeedContent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_order: Vec<String>, // Slugs in the author's order; set through reorder_posts
+}
+
+pub(crate) fn manifest_path(project_path: &Path) -> PathBuf {
+    project_path.join(MANIFEST_FILE)
+}
+
+pub(crate) fn load_manifest(project_path: &Path) -> Result<ProjectManifest, String> {
+    let path = manifest_path(project_path);
+    if !path.exists() {
+        return Ok(ProjectManifest::default());
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read project manifest: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse project manifest: {}", e))
+}
+
+pub(crate) fn save_manifest(project_path: &Path, manifest: &ProjectManifest) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(manifest)
+        .map_err(|e| format!("Failed to serialize project manifest: {}", e))?;
+    crate::write_file_atomic(&manifest_path(project_path), &content)
+}
+
+fn project_path(folder_name: &str) -> Result<PathBuf, String> {
+    crate::sections::resolve_project_path(&crate::studio_dir()?, folder_name)
+}
+
+#[tauri::command]
+pub async fn get_project_metadata(folder_name: String) -> Result<ProjectManifest, String> {
+    load_manifest(&project_path(&folder_name)?)
+}
+
+#[tauri::command]
+pub async fn update_project_metadata(
+    folder_name: String,
+    metadata: ProjectManifest,
+) -> Result<ProjectManifest, String> {
+    let project_path = project_path(&folder_name)?;
+
+    // Treat blank fields from the form as unset
+    let clean = |value: Option<String>| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let stored = load_manifest(&project_path)?;
+    let manifest = ProjectManifest {
+        name: stored.name,
+        description: clean(metadata.description),
+        author: clean(metadata.author),
+        language: clean(metadata.language),
+        cover: clean(metadata.cover),
+        feed_content: metadata.feed_content,
+        post_order: stored.post_order,
+    };
+
+    if let Some(cover) = &manifest.cover {
+        if !cover.starts_with('/') {
+            return Err("Cover must be a site path such as /media/project/cover.jpg".to_string());
+        }
+    }
+
+    save_manifest(&project_path, &manifest)?;
+    crate::refresh_site_files(&crate::studio_dir()?)?;
+    Ok(manifest)
+}
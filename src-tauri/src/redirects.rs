@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Redirect {
+    from: String, // Old URL, e.g. /project/old-slug
+    to: String,   // Current URL
+    #[serde(default)]
+    prefix: bool, // Also redirects everything below `from`, e.g. a renamed project folder
+    created_at: String,
+}
+
+fn redirects_path(studio_path: &Path) -> PathBuf {
+    studio_path.join(".studio").join("redirects.json")
+}
+
+pub(crate) fn load_redirects(studio_path: &Path) -> Result<Vec<Redirect>, String> {
+    let path = redirects_path(studio_path);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read redirects file: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse redirects file: {}", e))
+}
+
+fn save_redirects(studio_path: &Path, redirects: &[Redirect]) -> Result<(), String> {
+    let path = redirects_path(studio_path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create metadata directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(redirects)
+        .map_err(|e| format!("Failed to serialize redirects: {}", e))?;
+    crate::write_file_atomic(&path, &content)?;
+
+    // Keep the static host file in step with the table; astro.config.mjs reads
+    // redirects.json directly when the site is built
+    write_static_redirects(studio_path, redirects)
+}
+
+/// Writes `public/_redirects` in the format understood by Netlify and Cloudflare Pages.
+fn write_static_redirects(studio_path: &Path, redirects: &[Redirect]) -> Result<(), String> {
+    let public_path = studio_path.join("public");
+    fs::create_dir_all(&public_path)
+        .map_err(|e| format!("Failed to create public directory: {}", e))?;
+
+    let mut content =
+        String::from("# Generated by Studio Builder Desktop. Edit redirects in the app.\n");
+    for redirect in redirects {
+        content.push_str(&format!("{} {} 301\n", redirect.from, redirect.to));
+        if redirect.prefix {
+            content.push_str(&format!("{}/* {}/:splat 301\n", redirect.from, redirect.to));
+        }
+    }
+
+    crate::write_file_atomic(&public_path.join("_redirects"), &content)
+}
+
+/// Records that `from` now lives at `to`. Existing redirects that pointed at `from`
+/// (or below it, for a prefix move) are retargeted so readers never go through a
+/// chain of hops.
+pub(crate) fn record_redirect(
+    studio_path: &Path,
+    from: &str,
+    to: &str,
+    prefix: bool,
+) -> Result<(), String> {
+    let mut redirects = load_redirects(studio_path)?;
+
+    // Content now lives at `to`, so nothing may redirect away from it
+    redirects.retain(|redirect| redirect.from != from && redirect.from != to);
+    for redirect in redirects.iter_mut() {
+        if redirect.to == from {
+            redirect.to = to.to_string();
+        } else if prefix {
+            if let Some(rest) = redirect
+                .to
+                .strip_prefix(from)
+                .filter(|r| r.starts_with('/'))
+            {
+                redirect.to = format!("{}{}", to, rest);
+            }
+        }
+    }
+    // Moving content back to an old URL makes that redirect a loop
+    redirects.retain(|redirect| redirect.from != redirect.to);
+
+    if from != to {
+        redirects.push(Redirect {
+            from: from.to_string(),
+            to: to.to_string(),
+            prefix,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        });
+    }
+
+    save_redirects(studio_path, &redirects)
+}
+
+fn normalize_redirect_url(url: &str, allow_external: bool) -> Result<String, String> {
+    let url = url.trim();
+
+    if allow_external && (url.starts_with("https://") || url.starts_with("http://")) {
+        return Ok(url.to_string());
+    }
+
+    if !url.starts_with('/') || url.contains(char::is_whitespace) {
+        return Err(format!("'{}' is not a site path starting with '/'", url));
+    }
+
+    let trimmed = url.trim_end_matches('/');
+    Ok(if trimmed.is_empty() {
+        "/".to_string()
+    } else {
+        trimmed.to_string()
+    })
+}
+
+#[tauri::command]
+pub async fn list_redirects() -> Result<Vec<Redirect>, String> {
+    load_redirects(&crate::studio_dir()?)
+}
+
+#[tauri::command]
+pub async fn add_redirect(
+    from: String,
+    to: String,
+    prefix: Option<bool>,
+) -> Result<Vec<Redirect>, String> {
+    let from = normalize_redirect_url(&from, false)?;
+    let to = normalize_redirect_url(&to, true)?;
+
+    if from == to {
+        return Err("A redirect cannot point at itself".to_string());
+    }
+
+    let studio_path = crate::studio_dir()?;
+    record_redirect(&studio_path, &from, &to, prefix.unwrap_or(false))?;
+    load_redirects(&studio_path)
+}
+
+#[tauri::command]
+pub async fn remove_redirect(from: String) -> Result<Vec<Redirect>, String> {
+    let from = normalize_redirect_url(&from, false)?;
+    let studio_path = crate::studio_dir()?;
+
+    let mut redirects = load_redirects(&studio_path)?;
+    let count = redirects.len();
+    redirects.retain(|redirect| redirect.from != from);
+
+    if redirects.len() == count {
+        return Err(format!("No redirect from '{}' exists", from));
+    }
+
+    save_redirects(&studio_path, &redirects)?;
+    Ok(redirects)
+}
@@ -0,0 +1,174 @@
+use crate::project_manifest;
+use crate::sections;
+use serde::Serialize;
+use similar::TextDiff;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairAction {
+    Create,
+    Update,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameSource {
+    Manifest,  // _project.json
+    IndexPage, // displayName in a surviving index.astro
+    FolderName,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepairFile {
+    file: String,
+    action: RepairAction,
+    diff: String, // Unified diff from the current file to the regenerated one
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepairPlan {
+    folder_name: String,
+    display_name: String,
+    name_source: NameSource,
+    files: Vec<RepairFile>,
+}
+
+// Text every generated index page has, and what pages written before post
+// statuses existed lack: they list drafts and posts scheduled for later
+const GENERATED_INDEX_MARKER: &str = "Create your first post using Studio Builder Desktop!";
+const STATUS_FILTER: &str = "isPublished";
+
+/// The project's display name, from the first metadata that survived.
+fn recover_display_name(project_path: &Path, folder_name: &str) -> (String, NameSource) {
+    let manifest_name = project_manifest::load_manifest(project_path)
+        .ok()
+        .and_then(|manifest| manifest.name)
+        .filter(|name| !name.trim().is_empty());
+    if let Some(name) = manifest_name {
+        return (name, NameSource::Manifest);
+    }
+
+    if let Some(name) = crate::read_project_display_name(project_path) {
+        return (name, NameSource::IndexPage);
+    }
+
+    let folder = folder_name.rsplit('/').next().unwrap_or(folder_name);
+    (folder.to_string(), NameSource::FolderName)
+}
+
+fn plan_repair(studio_path: &Path, folder_name: &str) -> Result<RepairPlan, String> {
+    let project_path = sections::resolve_project_path(studio_path, folder_name)?;
+    let (display_name, name_source) = recover_display_name(&project_path, folder_name);
+
+    let files = crate::render_project_scaffold(&project_path, &display_name, folder_name)
+        .into_iter()
+        .map(|(file, generated)| {
+            let current = fs::read_to_string(project_path.join(file)).ok();
+            let action = match &current {
+                None => RepairAction::Create,
+                Some(current) if *current == generated => RepairAction::Unchanged,
+                Some(_) => RepairAction::Update,
+            };
+            let current = current.unwrap_or_default();
+            let diff = match action {
+                RepairAction::Unchanged => String::new(),
+                _ => TextDiff::from_lines(&current, &generated)
+                    .unified_diff()
+                    .header(&format!("a/{}", file), &format!("b/{}", file))
+                    .to_string(),
+            };
+            RepairFile {
+                file: file.to_string(),
+                action,
+                diff,
+            }
+        })
+        .collect();
+
+    Ok(RepairPlan {
+        folder_name: folder_name.to_string(),
+        display_name,
+        name_source,
+        files,
+    })
+}
+
+/// Regenerates generated project and section index pages that still list
+/// every post, so drafts stay off them. Returns the folders updated.
+pub(crate) fn upgrade_index_pages(studio_path: &Path) -> Result<Vec<String>, String> {
+    let pages_path = studio_path.join("src").join("pages");
+    let mut upgraded = Vec::new();
+    if !pages_path.exists() {
+        return Ok(upgraded);
+    }
+
+    let entries =
+        fs::read_dir(&pages_path).map_err(|e| format!("Failed to read pages directory: {}", e))?;
+    let mut projects: Vec<String> = entries
+        .flatten()
+        .filter(|entry| crate::adopt::is_managed(&entry.path()))
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| !name.starts_with('.') && !name.starts_with('_'))
+        .collect();
+    projects.sort();
+
+    for project in projects {
+        for folder_name in sections::section_paths(studio_path, &project)? {
+            let project_path = pages_path.join(&folder_name);
+            let current = fs::read_to_string(project_path.join("index.astro")).unwrap_or_default();
+            if !current.contains(GENERATED_INDEX_MARKER) || current.contains(STATUS_FILTER) {
+                continue;
+            }
+
+            let (display_name, _) = recover_display_name(&project_path, &folder_name);
+            let generated =
+                crate::render_project_scaffold(&project_path, &display_name, &folder_name);
+            if let Some((file, content)) = generated.iter().find(|(file, _)| *file == "index.astro")
+            {
+                crate::write_file_atomic(&project_path.join(file), content)?;
+                upgraded.push(folder_name);
+            }
+        }
+    }
+
+    Ok(upgraded)
+}
+
+/// Shows what `repair_project` would write, with a diff per scaffold file.
+#[tauri::command]
+pub async fn preview_project_repair(folder_name: String) -> Result<RepairPlan, String> {
+    plan_repair(&crate::studio_dir()?, &folder_name)
+}
+
+/// Regenerates a project's missing or outdated `index.astro` and `_layout.astro`
+/// with the same generator `create_project` uses, keeping its display name.
+/// Sections are repaired by passing their path, e.g. `docs/guides`.
+#[tauri::command]
+pub async fn repair_project(folder_name: String) -> Result<RepairPlan, String> {
+    let studio_path = crate::studio_dir()?;
+    let plan = plan_repair(&studio_path, &folder_name)?;
+    let project_path = sections::resolve_project_path(&studio_path, &folder_name)?;
+
+    let generated = crate::render_project_scaffold(&project_path, &plan.display_name, &folder_name);
+    for (file, content) in generated {
+        let unchanged = plan
+            .files
+            .iter()
+            .any(|planned| planned.file == file && planned.action == RepairAction::Unchanged);
+        if !unchanged {
+            crate::write_file_atomic(&project_path.join(file), &content)?;
+        }
+    }
+
+    let mut manifest = project_manifest::load_manifest(&project_path)?;
+    if manifest.name.as_deref() != Some(plan.display_name.as_str()) {
+        manifest.name = Some(plan.display_name.clone());
+        project_manifest::save_manifest(&project_path, &manifest)?;
+    }
+    crate::refresh_site_files(&studio_path)?;
+
+    Ok(plan)
+}
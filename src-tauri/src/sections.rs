@@ -0,0 +1,212 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A project or one of its sections, with its posts and nested sections.
+#[derive(Debug, Serialize)]
+pub struct ProjectTreeNode {
+    name: String, // Display name
+    path: String, // Below src/pages, e.g. `docs/guides`; pass as `project_name` to post commands
+    url: String,
+    posts: Vec<crate::Post>,
+    sections: Vec<ProjectTreeNode>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Section {
+    name: String,
+    path: String,
+    url: String,
+}
+
+/// Folders Astro serves: all but hidden ones and `_`-prefixed ones.
+fn is_visible_dir(path: &Path) -> bool {
+    path.is_dir()
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| !name.starts_with('.') && !name.starts_with('_'))
+}
+
+/// Whether the folder holds posts, directly or in a folder below it.
+pub(crate) fn has_posts(dir: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        let path = entry.path();
+        crate::post_format::is_post_file(&path) || (is_visible_dir(&path) && has_posts(&path))
+    })
+}
+
+/// A page the folder serves at its own URL, such as `index.astro` or `index.md`.
+pub(crate) fn has_index_page(dir: &Path) -> bool {
+    fs::read_dir(dir).is_ok_and(|entries| {
+        entries.flatten().any(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            entry.path().is_file()
+                && name
+                    .rsplit_once('.')
+                    .is_some_and(|(stem, _)| stem == "index")
+        })
+    })
+}
+
+/// Sections are visible folders with the scaffold's index page or posts.
+/// Folders of images and other files are neither.
+pub(crate) fn is_section_dir(path: &Path) -> bool {
+    is_visible_dir(path) && (path.join("index.astro").is_file() || has_posts(path))
+}
+
+fn child_sections(dir: &Path) -> Result<Vec<String>, String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_section_dir(path))
+        .filter_map(|path| path.file_name()?.to_str().map(str::to_string))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Resolves a project or section path such as `docs/guides` below src/pages,
+/// rejecting anything that could point outside it.
+pub(crate) fn resolve_project_path(studio_path: &Path, project: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(project);
+    let valid = !project.is_empty()
+        && relative.components().all(|component| match component {
+            Component::Normal(part) => part
+                .to_str()
+                .is_some_and(|part| !part.starts_with('.') && !part.starts_with('_')),
+            _ => false,
+        });
+    if !valid {
+        return Err(format!("'{}' is not a valid project path", project));
+    }
+
+    let project_path = studio_path.join("src").join("pages").join(relative);
+    if !project_path.is_dir() {
+        return Err(format!("Project '{}' does not exist", project));
+    }
+    Ok(project_path)
+}
+
+/// The project's own path followed by every section below it, depth first.
+pub(crate) fn section_paths(studio_path: &Path, project: &str) -> Result<Vec<String>, String> {
+    let pages_path = studio_path.join("src").join("pages");
+    let mut paths = vec![project.to_string()];
+    let mut index = 0;
+
+    while index < paths.len() {
+        let children = child_sections(&pages_path.join(&paths[index]))?;
+        let nested: Vec<String> = children
+            .into_iter()
+            .map(|child| format!("{}/{}", paths[index], child))
+            .collect();
+        // Insert right after the parent so the list stays depth first
+        let insert_at = index + 1;
+        paths.splice(insert_at..insert_at, nested);
+        index += 1;
+    }
+
+    Ok(paths)
+}
+
+fn build_tree(pages_path: &Path, path: &str) -> Result<ProjectTreeNode, String> {
+    let dir = pages_path.join(path);
+    let folder = path.rsplit('/').next().unwrap_or(path);
+
+    let mut sections = Vec::new();
+    for child in child_sections(&dir)? {
+        sections.push(build_tree(pages_path, &format!("{}/{}", path, child))?);
+    }
+
+    Ok(ProjectTreeNode {
+        name: crate::read_project_display_name(&dir).unwrap_or_else(|| folder.to_string()),
+        path: path.to_string(),
+        url: format!("/{}", path),
+        posts: crate::collect_posts(&dir, None)?,
+        sections,
+    })
+}
+
+#[tauri::command]
+pub async fn list_project_tree(folder_name: String) -> Result<ProjectTreeNode, String> {
+    let studio_path = crate::studio_dir()?;
+    resolve_project_path(&studio_path, &folder_name)?;
+    build_tree(&studio_path.join("src").join("pages"), &folder_name)
+}
+
+/// Creates a section inside a project or another section. Posts go into it by
+/// passing the returned `path` as the project name, and its URLs follow the folders.
+#[tauri::command]
+pub async fn create_section(project_name: String, name: String) -> Result<Section, String> {
+    let studio_path = crate::studio_dir()?;
+    let parent_path = resolve_project_path(&studio_path, &project_name)?;
+
+    let (name, folder) = crate::validation::validate(
+        crate::validation::NameKind::Section,
+        &parent_path,
+        &name,
+        None,
+    )
+    .into_result()?;
+
+    let section_path = parent_path.join(&folder);
+
+    fs::create_dir_all(&section_path)
+        .map_err(|e| format!("Failed to create section directory: {}", e))?;
+
+    let path = format!("{}/{}", project_name, folder);
+    crate::write_project_scaffold(&section_path, &name, &path)?;
+    crate::refresh_site_files(&studio_path)?;
+
+    Ok(Section {
+        name,
+        url: format!("/{}", path),
+        path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_studio(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("studio-sections-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src").join("pages")).unwrap();
+        dir
+    }
+
+    fn write(studio: &Path, relative: &str, content: &str) {
+        let path = studio.join("src").join("pages").join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn asset_folders_are_not_sections() {
+        let studio = temp_studio("assets");
+        write(&studio, "docs/index.astro", "");
+        write(&studio, "docs/intro.md", "# Intro\n");
+        write(&studio, "docs/images/cover.png", "png");
+        write(&studio, "docs/images/2024/photo.jpg", "jpg");
+        write(&studio, "docs/guides/index.astro", "");
+        write(&studio, "docs/notes/deep/first.md", "# First\n");
+        write(&studio, "docs/_drafts/hidden.md", "# Hidden\n");
+
+        assert_eq!(
+            section_paths(&studio, "docs").unwrap(),
+            ["docs", "docs/guides", "docs/notes", "docs/notes/deep"]
+        );
+        assert!(!is_section_dir(&studio.join("src/pages/docs/images")));
+        assert!(!has_index_page(&studio.join("src/pages/docs/notes")));
+
+        let _ = fs::remove_dir_all(&studio);
+    }
+}
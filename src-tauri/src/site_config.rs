@@ -0,0 +1,234 @@
+use crate::slug::SlugSettings;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+// Workspace-wide settings, kept at the root of the studio folder
+const CONFIG_FILE: &str = "studio.config.json";
+
+/// Workspace settings read by the app and by the site itself: astro.config.mjs
+/// and the starter layouts load the same file through src/lib/site-config.js.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SiteConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>, // Public base URL, e.g. https://example.com
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>, // BCP 47 tag for the pages' lang attribute, e.g. en or pt-BR
+    #[serde(default, skip_serializing_if = "ThemeColors::is_default")]
+    pub colors: ThemeColors,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nav: Vec<NavLink>,
+    #[serde(default, skip_serializing_if = "RobotsConfig::is_default")]
+    pub robots: RobotsConfig,
+    #[serde(default, skip_serializing_if = "SlugSettings::is_default")]
+    pub slugs: SlugSettings, // How post titles become URLs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub project_order: Vec<String>, // Folder names; set through reorder_projects
+    // Keep settings this version doesn't know about when rewriting the file
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Colors the layouts apply as CSS custom properties; unset ones keep the
+/// starter site's defaults. Values are hex colors such as `#1d4ed8`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThemeColors {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary: Option<String>, // Links and navigation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub muted: Option<String>, // Secondary text such as card descriptions
+}
+
+impl ThemeColors {
+    fn is_default(&self) -> bool {
+        *self == ThemeColors::default()
+    }
+}
+
+/// A link in the site-wide navigation bar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NavLink {
+    pub label: String,
+    pub href: String, // A site path such as /docs, or an absolute URL
+}
+
+/// Rules for the generated robots.txt.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RobotsConfig {
+    #[serde(default)]
+    pub disallow_all: bool, // Keep the whole site out of search engines, e.g. for staging
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disallow: Vec<String>, // Path prefixes such as /drafts
+}
+
+impl RobotsConfig {
+    fn is_default(&self) -> bool {
+        !self.disallow_all && self.disallow.is_empty()
+    }
+}
+
+impl SiteConfig {
+    /// The base URL without a trailing slash, if one is configured.
+    pub(crate) fn base_url(&self) -> Option<&str> {
+        self.url
+            .as_deref()
+            .map(|url| url.trim().trim_end_matches('/'))
+            .filter(|url| !url.is_empty())
+    }
+}
+
+pub(crate) fn load_site_config(studio_path: &Path) -> Result<SiteConfig, String> {
+    let path = studio_path.join(CONFIG_FILE);
+    if !path.exists() {
+        return Ok(SiteConfig::default());
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", CONFIG_FILE, e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", CONFIG_FILE, e))
+}
+
+pub(crate) fn save_site_config(studio_path: &Path, config: &SiteConfig) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize {}: {}", CONFIG_FILE, e))?;
+    crate::write_file_atomic(&studio_path.join(CONFIG_FILE), &content)
+}
+
+fn is_hex_color(value: &str) -> bool {
+    value.strip_prefix('#').is_some_and(|hex| {
+        matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit())
+    })
+}
+
+fn is_language_tag(value: &str) -> bool {
+    let mut parts = value.split('-');
+    let primary = parts.next().unwrap_or_default();
+    (2..=3).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && parts.all(|part| {
+            (1..=8).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+fn is_absolute_url(value: &str) -> bool {
+    ["http://", "https://"].iter().any(|scheme| {
+        value
+            .strip_prefix(scheme)
+            .is_some_and(|rest| !rest.is_empty() && !rest.starts_with('/'))
+    }) && !value.contains(char::is_whitespace)
+}
+
+/// Trims text fields, turns empty ones into unset ones, and checks the rest.
+fn normalize(mut config: SiteConfig) -> Result<SiteConfig, String> {
+    let trimmed = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    config.title = trimmed(config.title);
+    config.description = trimmed(config.description);
+    config.url = trimmed(config.url);
+    config.language = trimmed(config.language);
+
+    if let Some(url) = &config.url {
+        if !is_absolute_url(url) {
+            return Err(format!(
+                "Site URL '{}' must be an absolute http:// or https:// address",
+                url
+            ));
+        }
+    }
+    if let Some(language) = &config.language {
+        if !is_language_tag(language) {
+            return Err(format!(
+                "'{}' is not a language tag such as en or pt-BR",
+                language
+            ));
+        }
+    }
+
+    let colors = &mut config.colors;
+    for (name, color) in [
+        ("primary", &mut colors.primary),
+        ("background", &mut colors.background),
+        ("text", &mut colors.text),
+        ("muted", &mut colors.muted),
+    ] {
+        *color = trimmed(color.take());
+        if let Some(value) = color {
+            if !is_hex_color(value) {
+                return Err(format!(
+                    "The {} color '{}' must be a hex color such as #1d4ed8",
+                    name, value
+                ));
+            }
+        }
+    }
+
+    for link in &mut config.nav {
+        link.label = link.label.trim().to_string();
+        link.href = link.href.trim().to_string();
+        if link.label.is_empty() {
+            return Err("Navigation links need a label".to_string());
+        }
+        let valid_href =
+            link.href.starts_with('/') || link.href.starts_with('#') || is_absolute_url(&link.href);
+        if !valid_href {
+            return Err(format!(
+                "Navigation link '{}' must point to a site path such as /docs or a full URL",
+                link.label
+            ));
+        }
+    }
+
+    for path in &mut config.robots.disallow {
+        *path = path.trim().to_string();
+        if !path.starts_with('/') {
+            return Err(format!("Disallowed path '{}' must start with /", path));
+        }
+        // Each path is one robots.txt line
+        if path.contains(|c: char| c.is_whitespace() || c.is_control()) {
+            return Err(format!(
+                "Disallowed path '{}' can't contain spaces or line breaks",
+                path.escape_debug()
+            ));
+        }
+    }
+
+    config.slugs.validate()?;
+
+    Ok(config)
+}
+
+#[tauri::command]
+pub async fn get_site_config() -> Result<SiteConfig, String> {
+    load_site_config(&crate::studio_dir()?)
+}
+
+/// Validates and saves the site settings. The project order is kept as stored
+/// (it is set through reorder_projects), as are settings the editor doesn't send.
+#[tauri::command]
+pub async fn update_site_config(config: SiteConfig) -> Result<SiteConfig, String> {
+    let studio_path = crate::studio_dir()?;
+    let stored = load_site_config(&studio_path)?;
+
+    let mut config = normalize(config)?;
+    config.project_order = stored.project_order;
+    for (key, value) in stored.extra {
+        config.extra.entry(key).or_insert(value);
+    }
+
+    save_site_config(&studio_path, &config)?;
+    crate::refresh_site_files(&studio_path)?;
+
+    Ok(config)
+}
@@ -0,0 +1,107 @@
+use crate::frontmatter;
+use crate::post_format::{self, PostFormat};
+use crate::post_status::{self, PostStatus};
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::path::Path;
+
+/// A published post as the generated site presents it.
+pub(crate) struct SitePost {
+    pub project: String, // Project folder name
+    pub slug: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub date: Option<DateTime<Utc>>,
+    pub updated: DateTime<Utc>, // `updated`, then `date`, then the file's mtime
+    pub body: String,           // Markdown without the frontmatter
+}
+
+impl SitePost {
+    pub(crate) fn url_path(&self) -> String {
+        format!("/{}/{}", self.project, self.slug)
+    }
+}
+
+/// Folder names of every project in the workspace, sorted.
+pub(crate) fn project_folders(studio_path: &Path) -> Result<Vec<String>, String> {
+    let pages_path = studio_path.join("src").join("pages");
+    if !pages_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries =
+        fs::read_dir(&pages_path).map_err(|e| format!("Failed to read pages directory: {}", e))?;
+    let mut folders: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| !name.starts_with('.') && !name.starts_with('_'))
+        .filter(|name| !crate::taxonomy::is_tag_pages_dir(&pages_path, name))
+        .collect();
+    folders.sort();
+    Ok(folders)
+}
+
+/// Published posts of a project and all of its sections.
+pub(crate) fn published_posts_with_sections(
+    studio_path: &Path,
+    project: &str,
+) -> Result<Vec<SitePost>, String> {
+    let mut posts = Vec::new();
+    for section in crate::sections::section_paths(studio_path, project)? {
+        posts.extend(published_posts(studio_path, &section)?);
+    }
+    Ok(posts)
+}
+
+/// Reads the published posts directly inside a project or section (a path
+/// such as `docs/guides`) in listing order.
+pub(crate) fn published_posts(studio_path: &Path, project: &str) -> Result<Vec<SitePost>, String> {
+    let project_path = studio_path.join("src").join("pages").join(project);
+    let mut posts = Vec::new();
+
+    for slug in crate::ordered_post_slugs(&project_path)? {
+        let (path, format) = post_format::find_post_file(&project_path, &slug)
+            .ok_or_else(|| format!("Post '{}' does not exist", slug))?;
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read post '{}': {}", slug, e))?;
+        if post_status::read_post_state(&content).status != PostStatus::Published {
+            continue;
+        }
+
+        let date = frontmatter::get_field(&content, "date")
+            .and_then(|value| post_status::parse_publish_at(&value));
+        let updated = frontmatter::get_field(&content, "updated")
+            .and_then(|value| post_status::parse_publish_at(&value))
+            .or(date)
+            .or_else(|| {
+                fs::metadata(&path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .map(DateTime::<Utc>::from)
+            })
+            .unwrap_or_else(Utc::now);
+        let body = frontmatter::split(&content)
+            .map(|(_, body)| body)
+            .unwrap_or(&content);
+        let body = match format {
+            PostFormat::Mdx => post_format::strip_mdx_statements(body),
+            PostFormat::Md => body.to_string(),
+        };
+
+        posts.push(SitePost {
+            project: project.to_string(),
+            title: crate::extract_title_from_markdown(&content, &slug),
+            description: frontmatter::get_field(&content, "description")
+                .filter(|description| !description.is_empty()),
+            tags: frontmatter::get_list(&content, "tags"),
+            date,
+            updated,
+            body,
+            slug,
+        });
+    }
+
+    Ok(posts)
+}
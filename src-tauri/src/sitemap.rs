@@ -0,0 +1,256 @@
+use crate::sections;
+use crate::site_config::{self, RobotsConfig};
+use crate::site_posts;
+use crate::taxonomy;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+const SITEMAP_FILE: &str = "sitemap.xml";
+const ROBOTS_FILE: &str = "robots.txt";
+// Marks files the app owns; anything without it was written by hand and is left alone
+const GENERATED_MARKER: &str = "Generated by Studio Builder Desktop";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SitemapSummary {
+    path: String,
+    urls: usize,
+    robots_written: bool, // False when public/robots.txt was written by hand
+}
+
+struct SitemapUrl {
+    loc: String,
+    lastmod: Option<DateTime<Utc>>,
+}
+
+fn is_hand_written(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|content| !content.contains(GENERATED_MARKER))
+}
+
+/// Whether public/sitemap.xml was written by hand rather than by the app.
+pub(crate) fn is_hand_written_sitemap(studio_path: &Path) -> bool {
+    is_hand_written(&studio_path.join("public").join(SITEMAP_FILE))
+}
+
+/// Percent-encodes a URL path per RFC 3986, keeping `/` between segments and
+/// the characters a segment may hold as they are.
+pub(crate) fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => encoded.push(byte as char),
+            b'-' | b'.' | b'_' | b'~' | b'/' | b':' | b'@' => encoded.push(byte as char),
+            b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'=' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn sitemap_document(urls: &[SitemapUrl]) -> String {
+    let mut entries = String::new();
+    for url in urls {
+        entries.push_str(&format!(
+            "  <url>\n    <loc>{}</loc>\n",
+            crate::escape_xml(&url.loc)
+        ));
+        if let Some(lastmod) = url.lastmod {
+            entries.push_str(&format!(
+                "    <lastmod>{}</lastmod>\n",
+                lastmod.format("%Y-%m-%dT%H:%M:%SZ")
+            ));
+        }
+        entries.push_str("  </url>\n");
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!-- {} -->\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n{}</urlset>\n",
+        GENERATED_MARKER, entries
+    )
+}
+
+fn robots_document(robots: &RobotsConfig, sitemap_url: &str) -> String {
+    let mut lines = vec![
+        format!("# {}", GENERATED_MARKER),
+        "User-agent: *".to_string(),
+    ];
+
+    if robots.disallow_all {
+        lines.push("Disallow: /".to_string());
+    } else if robots.disallow.is_empty() {
+        lines.push("Allow: /".to_string());
+    } else {
+        for path in &robots.disallow {
+            let path = path.trim();
+            // Settings are checked on save, but studio.config.json can be edited by hand
+            if path.contains(|c: char| c.is_whitespace() || c.is_control()) {
+                continue;
+            }
+            if path.starts_with('/') {
+                lines.push(format!("Disallow: {}", path));
+            } else {
+                lines.push(format!("Disallow: /{}", path));
+            }
+        }
+    }
+
+    lines.push(String::new());
+    lines.push(format!("Sitemap: {}", sitemap_url));
+    lines.join("\n") + "\n"
+}
+
+/// Writes `public/sitemap.xml` and `public/robots.txt` so every site build
+/// ships them. Covers the home page, each project and every published post.
+pub(crate) fn write_sitemap(studio_path: &Path) -> Result<SitemapSummary, String> {
+    let config = site_config::load_site_config(studio_path)?;
+    let base_url = config
+        .base_url()
+        .ok_or("Set the site URL in studio.config.json before generating a sitemap")?
+        .to_string();
+
+    let public_path = studio_path.join("public");
+    fs::create_dir_all(&public_path)
+        .map_err(|e| format!("Failed to create public folder: {}", e))?;
+
+    let mut urls = vec![SitemapUrl {
+        loc: format!("{}/", base_url),
+        lastmod: None,
+    }];
+    let mut site_lastmod: Option<DateTime<Utc>> = None;
+    let mut tag_lastmods: BTreeMap<String, DateTime<Utc>> = BTreeMap::new();
+
+    let mut folders = Vec::new();
+    for project in site_posts::project_folders(studio_path)? {
+        folders.extend(sections::section_paths(studio_path, &project)?);
+    }
+
+    // Projects and their sections, each followed by its own posts
+    for folder in folders {
+        let posts = site_posts::published_posts(studio_path, &folder)?;
+        let folder_lastmod = posts.iter().map(|post| post.updated).max();
+        site_lastmod = site_lastmod.max(folder_lastmod);
+
+        // A folder of posts without an index page has nothing at its own URL
+        if sections::has_index_page(&studio_path.join("src").join("pages").join(&folder)) {
+            urls.push(SitemapUrl {
+                loc: format!("{}/{}", base_url, encode_path(&folder)),
+                lastmod: folder_lastmod,
+            });
+        }
+        urls.extend(posts.iter().map(|post| SitemapUrl {
+            loc: format!("{}{}", base_url, encode_path(&post.url_path())),
+            lastmod: Some(post.updated),
+        }));
+
+        for post in &posts {
+            for tag in &post.tags {
+                let lastmod = tag_lastmods.entry(taxonomy::tag_slug(tag)).or_default();
+                *lastmod = (*lastmod).max(post.updated);
+            }
+        }
+    }
+
+    // Tag pages exist once the workspace has the tag index scaffolded
+    let pages_path = studio_path.join("src").join("pages");
+    if taxonomy::is_tag_pages_dir(&pages_path, taxonomy::TAG_PAGES_DIR) {
+        urls.push(SitemapUrl {
+            loc: format!("{}/{}", base_url, taxonomy::TAG_PAGES_DIR),
+            lastmod: tag_lastmods.values().max().copied(),
+        });
+        urls.extend(
+            tag_lastmods
+                .iter()
+                .filter(|(slug, _)| !slug.is_empty())
+                .map(|(slug, lastmod)| SitemapUrl {
+                    loc: format!(
+                        "{}/{}/{}",
+                        base_url,
+                        taxonomy::TAG_PAGES_DIR,
+                        encode_path(slug)
+                    ),
+                    lastmod: Some(*lastmod),
+                }),
+        );
+    }
+    urls[0].lastmod = site_lastmod;
+
+    let sitemap_path = public_path.join(SITEMAP_FILE);
+    if is_hand_written(&sitemap_path) {
+        return Err(format!(
+            "public/{} was not generated by the app; remove it to let the app manage it",
+            SITEMAP_FILE
+        ));
+    }
+    crate::write_file_atomic(&sitemap_path, &sitemap_document(&urls))?;
+
+    let robots_path = public_path.join(ROBOTS_FILE);
+    let robots_written = !is_hand_written(&robots_path);
+    if robots_written {
+        let sitemap_url = format!("{}/{}", base_url, SITEMAP_FILE);
+        crate::write_file_atomic(&robots_path, &robots_document(&config.robots, &sitemap_url))?;
+    }
+
+    Ok(SitemapSummary {
+        path: sitemap_path.to_string_lossy().to_string(),
+        urls: urls.len(),
+        robots_written,
+    })
+}
+
+#[tauri::command]
+pub async fn generate_sitemap() -> Result<SitemapSummary, String> {
+    write_sitemap(&crate::studio_dir()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_encodes_path_segments() {
+        assert_eq!(encode_path("/blog/hello-world"), "/blog/hello-world");
+        assert_eq!(
+            encode_path("/blog/café über"),
+            "/blog/caf%C3%A9%20%C3%BCber"
+        );
+        assert_eq!(encode_path("/日本"), "/%E6%97%A5%E6%9C%AC");
+        assert_eq!(encode_path("/a?b#c%d"), "/a%3Fb%23c%25d");
+        assert_eq!(encode_path("/rock&roll"), "/rock&roll");
+    }
+
+    #[test]
+    fn leaves_asset_folders_out() {
+        let studio =
+            std::env::temp_dir().join(format!("studio-sitemap-assets-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&studio);
+        let pages = studio.join("src").join("pages");
+        for (relative, content) in [
+            ("docs/index.astro", ""),
+            ("docs/intro.md", "---\ntitle: Intro\n---\n"),
+            ("docs/images/cover.png", "png"),
+            ("docs/notes/first.md", "---\ntitle: First\n---\n"),
+        ] {
+            fs::create_dir_all(pages.join(relative).parent().unwrap()).unwrap();
+            fs::write(pages.join(relative), content).unwrap();
+        }
+        fs::write(
+            studio.join("studio.config.json"),
+            r#"{"url": "https://example.com"}"#,
+        )
+        .unwrap();
+
+        write_sitemap(&studio).unwrap();
+        let sitemap = fs::read_to_string(studio.join("public").join("sitemap.xml")).unwrap();
+        assert!(sitemap.contains("<loc>https://example.com/docs</loc>"));
+        assert!(sitemap.contains("<loc>https://example.com/docs/notes/first</loc>"));
+        assert!(!sitemap.contains("images"));
+        // The notes folder has posts but no index page of its own
+        assert!(!sitemap.contains("<loc>https://example.com/docs/notes</loc>"));
+
+        let _ = fs::remove_dir_all(&studio);
+    }
+}
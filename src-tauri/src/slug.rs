@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+/// Shorter limits make numbered collisions (`-1`, `-2`) the norm.
+const MIN_MAX_LENGTH: usize = 10;
+
+// Dropped from title-derived slugs when `remove_stop_words` is set
+const STOP_WORDS: [&str; 24] = [
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "from", "in", "into", "is",
+    "it", "its", "of", "on", "or", "that", "the", "to", "was", "with",
+];
+
+/// How letters outside ASCII end up in post slugs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlugStrategy {
+    #[default]
+    Unicode, // Keep letters from every script, e.g. café-über
+    Ascii,    // Transliterate to ASCII, keeping Chinese, Japanese and Korean as written
+    Romanize, // Transliterate everything: kana to romaji, Hangul romanized, Han (kanji too) to pinyin
+}
+
+/// Workspace settings for slugs made from post titles.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlugSettings {
+    #[serde(default)]
+    pub strategy: SlugStrategy,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>, // In characters; cut at a word boundary
+    #[serde(default)]
+    pub remove_stop_words: bool, // English words such as "the" and "of"
+}
+
+impl SlugSettings {
+    pub(crate) fn is_default(&self) -> bool {
+        *self == SlugSettings::default()
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        match self.max_length {
+            Some(max_length) if max_length < MIN_MAX_LENGTH => Err(format!(
+                "The slug length limit must be at least {} characters",
+                MIN_MAX_LENGTH
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The slug settings in studio.config.json, or the defaults.
+pub(crate) fn workspace_settings() -> SlugSettings {
+    crate::studio_dir()
+        .and_then(|studio_path| crate::site_config::load_site_config(&studio_path))
+        .map(|config| config.slugs)
+        .unwrap_or_default()
+}
+
+/// Chinese, Japanese and Korean characters. They transliterate one character
+/// at a time, so kanji get their Chinese readings; `ascii` leaves them alone.
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}'
+        | '\u{3040}'..='\u{30FF}'
+        | '\u{3130}'..='\u{318F}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{AC00}'..='\u{D7AF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF66}'..='\u{FF9F}'
+        | '\u{20000}'..='\u{2FFFF}')
+}
+
+/// Han characters, which are words of their own rather than syllables.
+fn is_han(c: char) -> bool {
+    matches!(c,
+        '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2FFFF}')
+}
+
+fn transliterate(text: &str, strategy: SlugStrategy) -> String {
+    let text: String = text.nfc().collect();
+    if strategy == SlugStrategy::Unicode {
+        return text;
+    }
+
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii() || (strategy == SlugStrategy::Ascii && is_cjk(c)) {
+            output.push(c);
+        } else {
+            // Keep the reading of each Han character a word of its own
+            match deunicode::deunicode_char(c) {
+                Some(ascii) if is_han(c) => {
+                    output.push(' ');
+                    output.push_str(ascii.trim());
+                    output.push(' ');
+                }
+                Some(ascii) => output.push_str(ascii),
+                None => output.push(' '),
+            }
+        }
+    }
+    output
+}
+
+/// A slug typed by the user: transliterated, but otherwise kept as written.
+pub(crate) fn explicit_slug(slug: &str, settings: &SlugSettings) -> String {
+    crate::sanitize_slug(&transliterate(slug, settings.strategy))
+}
+
+/// The slug for a post title under the workspace's settings.
+pub(crate) fn title_slug(title: &str, settings: &SlugSettings) -> String {
+    let slug = explicit_slug(title, settings);
+    let mut words: Vec<&str> = slug.split('-').collect();
+
+    if settings.remove_stop_words {
+        let kept: Vec<&str> = words
+            .iter()
+            .copied()
+            .filter(|word| !STOP_WORDS.contains(word))
+            .collect();
+        // A title made only of stop words keeps them
+        if !kept.is_empty() {
+            words = kept;
+        }
+    }
+
+    match settings.max_length {
+        Some(max_length) => truncate_words(&words, max_length),
+        None => words.join("-"),
+    }
+}
+
+/// Joins as many whole words as fit; a first word that doesn't fit is cut.
+fn truncate_words(words: &[&str], max_length: usize) -> String {
+    let mut slug = String::new();
+    let mut length = 0;
+
+    for word in words {
+        let separator = usize::from(!slug.is_empty());
+        let word_length = word.chars().count();
+        if length + separator + word_length > max_length {
+            if slug.is_empty() {
+                slug = word.chars().take(max_length).collect();
+            }
+            break;
+        }
+        if separator == 1 {
+            slug.push('-');
+        }
+        slug.push_str(word);
+        length += separator + word_length;
+    }
+
+    slug
+}
+
+/// The slug `create_post` would give a title, before collisions are numbered.
+/// Pass `settings` to try settings before saving them; the workspace's are used otherwise.
+#[tauri::command]
+pub async fn preview_slug(title: String, settings: Option<SlugSettings>) -> Result<String, String> {
+    let settings = settings.unwrap_or_else(workspace_settings);
+    settings.validate()?;
+    Ok(title_slug(&title, &settings))
+}
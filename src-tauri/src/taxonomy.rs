@@ -0,0 +1,285 @@
+use crate::frontmatter;
+use crate::post_status::{self, PostStatus};
+use crate::site_posts;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Folder under src/pages holding the tag index pages; it is not a project.
+pub(crate) const TAG_PAGES_DIR: &str = "tags";
+// The dynamic route that tells the generated tag pages from a project named `tags`
+const TAG_PAGE_ROUTE: &str = "[tag].astro";
+
+// The same files the starter site ships, for workspaces created before them
+const TAG_PAGE_FILES: [(&str, &str); 3] = [
+    (
+        "src/lib/taxonomy.js",
+        include_str!("../resources/templates/starter-site/src/lib/taxonomy.js"),
+    ),
+    (
+        "src/pages/tags/index.astro",
+        include_str!("../resources/templates/starter-site/src/pages/tags/index.astro"),
+    ),
+    (
+        "src/pages/tags/[tag].astro",
+        include_str!("../resources/templates/starter-site/src/pages/tags/[tag].astro"),
+    ),
+];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Taxonomy {
+    #[default]
+    Tags,
+    Categories,
+}
+
+impl Taxonomy {
+    fn field(self) -> &'static str {
+        match self {
+            Taxonomy::Tags => "tags",
+            Taxonomy::Categories => "categories",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagCount {
+    name: String,
+    count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TaggedPost {
+    project: String,
+    slug: String,
+    title: String,
+    status: PostStatus,
+}
+
+struct PostFile {
+    project: String,
+    slug: String,
+    path: PathBuf,
+    content: String,
+}
+
+/// Whether `folder` below src/pages is the generated tag pages folder rather
+/// than a project that happens to be called `tags`.
+pub(crate) fn is_tag_pages_dir(pages_path: &Path, folder: &str) -> bool {
+    folder == TAG_PAGES_DIR && pages_path.join(folder).join(TAG_PAGE_ROUTE).is_file()
+}
+
+/// The URL segment of a tag's page; matches `tagSlug` in src/lib/taxonomy.js.
+pub(crate) fn tag_slug(tag: &str) -> String {
+    tag.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn read_all_posts(studio_path: &Path) -> Result<Vec<PostFile>, String> {
+    let mut posts = Vec::new();
+
+    let mut folders = Vec::new();
+    for project in site_posts::project_folders(studio_path)? {
+        folders.extend(crate::sections::section_paths(studio_path, &project)?);
+    }
+
+    for project in folders {
+        let project_path = studio_path.join("src").join("pages").join(&project);
+        for slug in crate::ordered_post_slugs(&project_path)? {
+            let path = crate::post_format::post_file_path(&project_path, &slug);
+            let content = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read post '{}/{}': {}", project, slug, e))?;
+            posts.push(PostFile {
+                project: project.clone(),
+                slug,
+                path,
+                content,
+            });
+        }
+    }
+
+    Ok(posts)
+}
+
+/// Writes every change or none of them: all new contents are staged in temp
+/// files first, and files already replaced are restored if a rename fails.
+fn write_all_or_nothing(changes: &[(&PostFile, String)]) -> Result<(), String> {
+    let temp_path = |path: &Path| {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        path.with_file_name(format!(".{}.tmp", name))
+    };
+
+    for (index, (post, content)) in changes.iter().enumerate() {
+        if let Err(e) = fs::write(temp_path(&post.path), content) {
+            for (staged, _) in &changes[..index] {
+                let _ = fs::remove_file(temp_path(&staged.path));
+            }
+            return Err(format!("Failed to stage '{}': {}", post.slug, e));
+        }
+    }
+
+    for (index, (post, _)) in changes.iter().enumerate() {
+        if let Err(e) = fs::rename(temp_path(&post.path), &post.path) {
+            for (replaced, _) in &changes[..index] {
+                let _ = fs::write(&replaced.path, &replaced.content);
+            }
+            for (pending, _) in &changes[index..] {
+                let _ = fs::remove_file(temp_path(&pending.path));
+            }
+            return Err(format!("Failed to update '{}': {}", post.slug, e));
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces any of `sources` with `target` in every post's list and returns
+/// the `project/slug` of each post that changed.
+fn replace_terms(
+    taxonomy: Taxonomy,
+    sources: &[String],
+    target: &str,
+) -> Result<Vec<String>, String> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err("Tag name cannot be empty".to_string());
+    }
+
+    let studio_path = crate::studio_dir()?;
+    let posts = read_all_posts(&studio_path)?;
+    let field = taxonomy.field();
+
+    let mut changes = Vec::new();
+    for post in &posts {
+        let terms = frontmatter::get_list(&post.content, field);
+        if !terms.iter().any(|term| sources.contains(term)) {
+            continue;
+        }
+
+        let mut updated: Vec<String> = Vec::with_capacity(terms.len());
+        for term in terms {
+            let term = if sources.contains(&term) {
+                target.to_string()
+            } else {
+                term
+            };
+            if !updated.contains(&term) {
+                updated.push(term);
+            }
+        }
+
+        let content =
+            frontmatter::set_field(&post.content, field, &frontmatter::format_list(&updated));
+        changes.push((post, content));
+    }
+
+    write_all_or_nothing(&changes)?;
+    if !changes.is_empty() {
+        crate::refresh_site_files(&studio_path)?;
+    }
+
+    Ok(changes
+        .iter()
+        .map(|(post, _)| format!("{}/{}", post.project, post.slug))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn list_tags(taxonomy: Option<Taxonomy>) -> Result<Vec<TagCount>, String> {
+    let field = taxonomy.unwrap_or_default().field();
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+
+    for post in read_all_posts(&crate::studio_dir()?)? {
+        for term in frontmatter::get_list(&post.content, field) {
+            *counts.entry(term).or_default() += 1;
+        }
+    }
+
+    let mut tags: Vec<TagCount> = counts
+        .into_iter()
+        .map(|(name, count)| TagCount { name, count })
+        .collect();
+    tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    Ok(tags)
+}
+
+#[tauri::command]
+pub async fn posts_by_tag(
+    tag: String,
+    taxonomy: Option<Taxonomy>,
+) -> Result<Vec<TaggedPost>, String> {
+    let field = taxonomy.unwrap_or_default().field();
+
+    Ok(read_all_posts(&crate::studio_dir()?)?
+        .into_iter()
+        .filter(|post| frontmatter::get_list(&post.content, field).contains(&tag))
+        .map(|post| TaggedPost {
+            title: crate::extract_title_from_markdown(&post.content, &post.slug),
+            status: post_status::read_post_state(&post.content).status,
+            project: post.project,
+            slug: post.slug,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn rename_tag(
+    old_name: String,
+    new_name: String,
+    taxonomy: Option<Taxonomy>,
+) -> Result<Vec<String>, String> {
+    if old_name == new_name.trim() {
+        return Err("The new tag name is the same as the old one".to_string());
+    }
+    replace_terms(taxonomy.unwrap_or_default(), &[old_name], &new_name)
+}
+
+#[tauri::command]
+pub async fn merge_tags(
+    sources: Vec<String>,
+    target: String,
+    taxonomy: Option<Taxonomy>,
+) -> Result<Vec<String>, String> {
+    if sources.is_empty() {
+        return Err("Choose at least one tag to merge".to_string());
+    }
+    replace_terms(taxonomy.unwrap_or_default(), &sources, &target)
+}
+
+/// Adds the tag index pages to a workspace that doesn't have them yet.
+/// Existing files are kept unless `force` is set. A project already using the
+/// folder is never written into.
+#[tauri::command]
+pub async fn scaffold_tag_pages(force: Option<bool>) -> Result<Vec<String>, String> {
+    let studio_path = crate::studio_dir()?;
+    let force = force.unwrap_or(false);
+
+    let pages_path = studio_path.join("src").join("pages");
+    if pages_path.join(TAG_PAGES_DIR).exists() && !is_tag_pages_dir(&pages_path, TAG_PAGES_DIR) {
+        return Err(format!(
+            "src/pages/{} holds a project; rename it before adding the tag pages",
+            TAG_PAGES_DIR
+        ));
+    }
+    let mut written = Vec::new();
+
+    for (relative, content) in TAG_PAGE_FILES {
+        let path = studio_path.join(relative);
+        if path.exists() && !force {
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        crate::write_file_atomic(&path, content)?;
+        written.push(relative.to_string());
+    }
+
+    Ok(written)
+}
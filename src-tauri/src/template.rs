@@ -0,0 +1,543 @@
+use serde::{Deserialize, Serialize};
+use similar::{capture_diff_slices, Algorithm, DiffTag, TextDiff};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// Version of resources/templates/starter-site. Bump it with changes to the
+/// template; updates are detected from file contents either way.
+const TEMPLATE_VERSION: &str = "1.2.0";
+
+// Stamp and pristine copies of the template as last installed, the common
+// ancestor for merging a newer template with the author's changes
+const STAMP_FILE: &str = "template.json";
+const BASE_DIR: &str = "template";
+
+// Template files as shipped before the stamp, one folder per release with the
+// files it changed. Unstamped workspaces merge from the closest of them
+const HISTORY_PATH: &str = "resources/templates/starter-site-history";
+
+// Managed by the package manager, not merged
+const SKIPPED_FILES: [&str; 1] = ["bun.lock"];
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TemplateStamp {
+    version: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateFileStatus {
+    Create,   // New in the template
+    Update,   // Changed in the template only
+    Merge,    // Changed in both; merges cleanly
+    Conflict, // Changed in both; needs the author's attention
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateFileChange {
+    path: String,
+    status: TemplateFileStatus,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateUpdateCheck {
+    installed_version: Option<String>, // None for workspaces created before stamping
+    bundled_version: String,
+    update_available: bool,
+    files: Vec<TemplateFileChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateConflict {
+    path: String,
+    merged: String, // The merge with conflict markers; the file itself is untouched
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct TemplateUpgradeResult {
+    version: String,
+    created: Vec<String>,
+    updated: Vec<String>,
+    merged: Vec<String>,
+    conflicts: Vec<TemplateConflict>,
+}
+
+struct MergeOutcome {
+    content: String,
+    conflicted: bool,
+}
+
+/// A change one side made to the base: lines `start..end` replaced by `lines`.
+struct Hunk<'a> {
+    start: usize,
+    end: usize,
+    lines: Vec<&'a str>,
+}
+
+fn template_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .resolve(
+            "resources/templates/starter-site",
+            tauri::path::BaseDirectory::Resource,
+        )
+        .map_err(|e| format!("Failed to resolve template path: {}", e))
+}
+
+fn history_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .resolve(HISTORY_PATH, tauri::path::BaseDirectory::Resource)
+        .map_err(|e| format!("Failed to resolve template history path: {}", e))
+}
+
+fn studio_meta_dir(studio_path: &Path) -> PathBuf {
+    studio_path.join(".studio")
+}
+
+fn collect_files(dir: &Path, prefix: &str, files: &mut Vec<String>) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') || SKIPPED_FILES.contains(&name.as_str()) {
+            continue;
+        }
+        let relative = if prefix.is_empty() {
+            name
+        } else {
+            format!("{}/{}", prefix, name)
+        };
+
+        if entry.path().is_dir() {
+            collect_files(&entry.path(), &relative, files)?;
+        } else {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
+
+fn template_files(template_path: &Path) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    collect_files(template_path, "", &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn load_stamp(studio_path: &Path) -> TemplateStamp {
+    fs::read_to_string(studio_meta_dir(studio_path).join(STAMP_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_stamp(studio_path: &Path, version: &str) -> Result<(), String> {
+    let stamp = TemplateStamp {
+        version: Some(version.to_string()),
+    };
+    let content = serde_json::to_string_pretty(&stamp)
+        .map_err(|e| format!("Failed to serialize template stamp: {}", e))?;
+    fs::create_dir_all(studio_meta_dir(studio_path))
+        .map_err(|e| format!("Failed to create .studio directory: {}", e))?;
+    crate::write_file_atomic(&studio_meta_dir(studio_path).join(STAMP_FILE), &content)
+}
+
+fn write_base(studio_path: &Path, relative: &str, content: &str) -> Result<(), String> {
+    let path = studio_meta_dir(studio_path).join(BASE_DIR).join(relative);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    crate::write_file_atomic(&path, content)
+}
+
+/// Stamps a freshly copied workspace and keeps a pristine copy of each
+/// template file for merging later upgrades.
+pub(crate) fn record_template(template_path: &Path, studio_path: &Path) -> Result<(), String> {
+    for relative in template_files(template_path)? {
+        let content = fs::read_to_string(template_path.join(&relative))
+            .map_err(|e| format!("Failed to read template file {}: {}", relative, e))?;
+        write_base(studio_path, &relative, &content)?;
+    }
+    save_stamp(studio_path, TEMPLATE_VERSION)
+}
+
+fn hunks<'a>(base: &[&str], other: &[&'a str]) -> Vec<Hunk<'a>> {
+    capture_diff_slices(Algorithm::Myers, base, other)
+        .into_iter()
+        .filter(|op| op.tag() != DiffTag::Equal)
+        .map(|op| {
+            let (_, old_range, new_range) = op.as_tag_tuple();
+            Hunk {
+                start: old_range.start,
+                end: old_range.end,
+                lines: other[new_range].to_vec(),
+            }
+        })
+        .collect()
+}
+
+/// One side's text for base lines `start..end`, with its hunks applied.
+fn apply_hunks(base: &[&str], start: usize, end: usize, hunks: &[&Hunk]) -> String {
+    let mut text = String::new();
+    let mut position = start;
+    for hunk in hunks {
+        text.extend(base[position..hunk.start].iter().copied());
+        text.extend(hunk.lines.iter().copied());
+        position = hunk.end;
+    }
+    text.extend(base[position..end].iter().copied());
+    text
+}
+
+fn push_conflict_side(output: &mut String, text: &str) {
+    output.push_str(text);
+    if !text.is_empty() && !text.ends_with('\n') {
+        output.push('\n');
+    }
+}
+
+/// Line-based three-way merge. Changes from either side that touch different
+/// lines are combined; overlapping different changes get conflict markers.
+fn merge_three_way(base: &str, ours: &str, theirs: &str) -> MergeOutcome {
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let our_lines: Vec<&str> = ours.split_inclusive('\n').collect();
+    let their_lines: Vec<&str> = theirs.split_inclusive('\n').collect();
+    let our_hunks = hunks(&base_lines, &our_lines);
+    let their_hunks = hunks(&base_lines, &their_lines);
+
+    let mut content = String::new();
+    let mut conflicted = false;
+    let mut position = 0;
+    let (mut ours_index, mut theirs_index) = (0, 0);
+
+    loop {
+        let next_ours = our_hunks.get(ours_index).map(|hunk| hunk.start);
+        let next_theirs = their_hunks.get(theirs_index).map(|hunk| hunk.start);
+        let group_start = match (next_ours, next_theirs) {
+            (None, None) => break,
+            (Some(start), None) | (None, Some(start)) => start,
+            (Some(a), Some(b)) => a.min(b),
+        };
+
+        // Gather every hunk from either side that touches the group's range
+        let mut group_end = group_start;
+        let mut ours_group: Vec<&Hunk> = Vec::new();
+        let mut theirs_group: Vec<&Hunk> = Vec::new();
+        loop {
+            let touches = |hunk: &Hunk| {
+                hunk.start < group_end
+                    || hunk.start == group_start
+                    || (hunk.start == group_end && hunk.start == hunk.end)
+            };
+            if let Some(hunk) = our_hunks.get(ours_index).filter(|hunk| touches(hunk)) {
+                group_end = group_end.max(hunk.end);
+                ours_group.push(hunk);
+                ours_index += 1;
+            } else if let Some(hunk) = their_hunks.get(theirs_index).filter(|hunk| touches(hunk)) {
+                group_end = group_end.max(hunk.end);
+                theirs_group.push(hunk);
+                theirs_index += 1;
+            } else {
+                break;
+            }
+        }
+
+        content.extend(base_lines[position..group_start].iter().copied());
+        let our_text = apply_hunks(&base_lines, group_start, group_end, &ours_group);
+        let their_text = apply_hunks(&base_lines, group_start, group_end, &theirs_group);
+
+        if theirs_group.is_empty() || our_text == their_text {
+            content.push_str(&our_text);
+        } else if ours_group.is_empty() {
+            content.push_str(&their_text);
+        } else {
+            conflicted = true;
+            content.push_str("<<<<<<< your version\n");
+            push_conflict_side(&mut content, &our_text);
+            content.push_str("=======\n");
+            push_conflict_side(&mut content, &their_text);
+            content.push_str(&format!(">>>>>>> template {}\n", TEMPLATE_VERSION));
+        }
+        position = group_end;
+    }
+
+    content.extend(base_lines[position..].iter().copied());
+    MergeOutcome {
+        content,
+        conflicted,
+    }
+}
+
+/// The shipped version of `relative` an unstamped workspace most likely
+/// started from: the one `ours` matches, or else the one closest to it.
+fn legacy_base(history_path: &Path, relative: &str, ours: Option<&str>) -> Option<String> {
+    let mut releases: Vec<PathBuf> = fs::read_dir(history_path)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    releases.sort();
+
+    let mut candidates = releases
+        .iter()
+        .filter_map(|release| fs::read_to_string(release.join(relative)).ok());
+    match ours {
+        Some(ours) => candidates.max_by(|a, b| {
+            let similarity = |base: &str| TextDiff::from_lines(base, ours).ratio();
+            similarity(a).total_cmp(&similarity(b))
+        }),
+        None => candidates.next_back(),
+    }
+}
+
+/// What upgrading one file would do, with the content it would write.
+/// `None` means the file is already up to date.
+fn plan_file(
+    template_path: &Path,
+    history_path: &Path,
+    studio_path: &Path,
+    relative: &str,
+) -> Result<Option<(TemplateFileStatus, String)>, String> {
+    let theirs = fs::read_to_string(template_path.join(relative))
+        .map_err(|e| format!("Failed to read template file {}: {}", relative, e))?;
+    let ours = fs::read_to_string(studio_path.join(relative)).ok();
+    let base = match fs::read_to_string(studio_meta_dir(studio_path).join(BASE_DIR).join(relative))
+    {
+        Ok(base) => Some(base),
+        // Workspaces from before the stamp kept no copy; use the shipped one
+        Err(_) if load_stamp(studio_path).version.is_none() => {
+            legacy_base(history_path, relative, ours.as_deref())
+        }
+        Err(_) => None,
+    };
+
+    let Some(ours) = ours else {
+        // Removed by the author after it was installed: respect that
+        return Ok(match base {
+            Some(_) => None,
+            None => Some((TemplateFileStatus::Create, theirs)),
+        });
+    };
+
+    if ours == theirs || base.as_deref() == Some(theirs.as_str()) {
+        return Ok(None);
+    }
+
+    Ok(Some(match base {
+        Some(base) if base == ours => (TemplateFileStatus::Update, theirs),
+        Some(base) => {
+            let outcome = merge_three_way(&base, &ours, &theirs);
+            let status = if outcome.conflicted {
+                TemplateFileStatus::Conflict
+            } else {
+                TemplateFileStatus::Merge
+            };
+            (status, outcome.content)
+        }
+        // Added by the author under a name the template now uses
+        None => {
+            let outcome = merge_three_way("", &ours, &theirs);
+            (TemplateFileStatus::Conflict, outcome.content)
+        }
+    }))
+}
+
+#[tauri::command]
+pub async fn check_template_updates(app: AppHandle) -> Result<TemplateUpdateCheck, String> {
+    let template_path = template_dir(&app)?;
+    let history_path = history_dir(&app)?;
+    let studio_path = crate::studio_dir()?;
+
+    let mut files = Vec::new();
+    for relative in template_files(&template_path)? {
+        if let Some((status, _)) =
+            plan_file(&template_path, &history_path, &studio_path, &relative)?
+        {
+            files.push(TemplateFileChange {
+                path: relative,
+                status,
+            });
+        }
+    }
+
+    let installed_version = load_stamp(&studio_path).version;
+    Ok(TemplateUpdateCheck {
+        update_available: !files.is_empty()
+            || installed_version.as_deref() != Some(TEMPLATE_VERSION),
+        installed_version,
+        bundled_version: TEMPLATE_VERSION.to_string(),
+        files,
+    })
+}
+
+/// Brings the workspace's copy of the starter template up to date, merging
+/// template changes with the author's. Conflicting files are left untouched
+/// and reported with the conflict-marked merge. Paths in `resolved` are kept
+/// as the author now has them and count as up to date.
+#[tauri::command]
+pub async fn upgrade_template(
+    app: AppHandle,
+    resolved: Option<Vec<String>>,
+) -> Result<TemplateUpgradeResult, String> {
+    let template_path = template_dir(&app)?;
+    let history_path = history_dir(&app)?;
+    let studio_path = crate::studio_dir()?;
+    let resolved = resolved.unwrap_or_default();
+
+    let mut result = TemplateUpgradeResult {
+        version: TEMPLATE_VERSION.to_string(),
+        ..Default::default()
+    };
+
+    for relative in template_files(&template_path)? {
+        let theirs = fs::read_to_string(template_path.join(&relative))
+            .map_err(|e| format!("Failed to read template file {}: {}", relative, e))?;
+
+        if !resolved.contains(&relative) {
+            let Some((status, content)) =
+                plan_file(&template_path, &history_path, &studio_path, &relative)?
+            else {
+                write_base(&studio_path, &relative, &theirs)?;
+                continue;
+            };
+
+            if status == TemplateFileStatus::Conflict {
+                result.conflicts.push(TemplateConflict {
+                    path: relative,
+                    merged: content,
+                });
+                continue;
+            }
+
+            let target = studio_path.join(&relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            crate::write_file_atomic(&target, &content)?;
+            match status {
+                TemplateFileStatus::Create => result.created.push(relative.clone()),
+                TemplateFileStatus::Update => result.updated.push(relative.clone()),
+                _ => result.merged.push(relative.clone()),
+            }
+        }
+
+        write_base(&studio_path, &relative, &theirs)?;
+    }
+
+    // Stay on the old version until every conflict is resolved
+    if result.conflicts.is_empty() {
+        save_stamp(&studio_path, TEMPLATE_VERSION)?;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "one\ntwo\nthree\nfour\nfive\n";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("studio-template-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(dir: &Path, relative: &str, content: &str) {
+        let path = dir.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn merges_changes_to_different_lines() {
+        let ours = "one\nTWO\nthree\nfour\nfive\n";
+        let theirs = "one\ntwo\nthree\nfour\nFIVE\nsix\n";
+        let outcome = merge_three_way(BASE, ours, theirs);
+        assert!(!outcome.conflicted);
+        assert_eq!(outcome.content, "one\nTWO\nthree\nfour\nFIVE\nsix\n");
+    }
+
+    #[test]
+    fn marks_overlapping_edits_as_a_conflict() {
+        let ours = "one\ntwo\nmine\nfour\nfive\n";
+        let theirs = "one\ntwo\nyours\nfour\nfive\n";
+        let outcome = merge_three_way(BASE, ours, theirs);
+        assert!(outcome.conflicted);
+        assert_eq!(
+            outcome.content,
+            format!(
+                "one\ntwo\n<<<<<<< your version\nmine\n=======\nyours\n>>>>>>> template {}\nfour\nfive\n",
+                TEMPLATE_VERSION
+            )
+        );
+    }
+
+    #[test]
+    fn merges_edits_to_adjacent_lines() {
+        let ours = "one\nTWO\nthree\nfour\nfive\n";
+        let theirs = "one\ntwo\nTHREE\nfour\nfive\n";
+        let outcome = merge_three_way(BASE, ours, theirs);
+        assert!(!outcome.conflicted);
+        assert_eq!(outcome.content, "one\nTWO\nTHREE\nfour\nfive\n");
+    }
+
+    #[test]
+    fn insertions_at_the_same_line_conflict_unless_identical() {
+        let ours = "one\ntwo\nmine\nthree\nfour\nfive\n";
+        let theirs = "one\ntwo\nyours\nthree\nfour\nfive\n";
+        let outcome = merge_three_way(BASE, ours, theirs);
+        assert!(outcome.conflicted);
+        assert!(outcome
+            .content
+            .starts_with("one\ntwo\n<<<<<<< your version\nmine\n=======\nyours\n>>>>>>>"));
+        assert!(outcome.content.ends_with("\nthree\nfour\nfive\n"));
+
+        let outcome = merge_three_way(BASE, ours, ours);
+        assert!(!outcome.conflicted);
+        assert_eq!(outcome.content, ours);
+    }
+
+    #[test]
+    fn unstamped_workspaces_merge_from_the_closest_shipped_version() {
+        let root = temp_dir("legacy");
+        let (template, history, studio) = (
+            root.join("template"),
+            root.join("history"),
+            root.join("studio"),
+        );
+        let relative = "src/pages/index.astro";
+        write(&history, &format!("01/{}", relative), BASE);
+        write(&history, "02/README.md", "Shipped later\n");
+        write(&template, relative, "one\ntwo\nthree\nfour\nFIVE\n");
+
+        // Left as shipped: a plain update
+        write(&studio, relative, BASE);
+        let (status, content) = plan_file(&template, &history, &studio, relative)
+            .unwrap()
+            .unwrap();
+        assert_eq!(status, TemplateFileStatus::Update);
+        assert_eq!(content, "one\ntwo\nthree\nfour\nFIVE\n");
+
+        // Edited by the author: merged rather than a whole-file conflict
+        write(&studio, relative, "ONE\ntwo\nthree\nfour\nfive\n");
+        let (status, content) = plan_file(&template, &history, &studio, relative)
+            .unwrap()
+            .unwrap();
+        assert_eq!(status, TemplateFileStatus::Merge);
+        assert_eq!(content, "ONE\ntwo\nthree\nfour\nFIVE\n");
+
+        // Removed by the author: left removed
+        fs::remove_file(studio.join(relative)).unwrap();
+        assert!(plan_file(&template, &history, &studio, relative)
+            .unwrap()
+            .is_none());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,321 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+// Each theme is a folder of workspace files (layouts and CSS) plus this manifest
+const THEME_MANIFEST: &str = "theme.json";
+// Which theme the workspace uses and the hash of each file as it was installed
+const THEME_STATE_FILE: &str = "theme.json";
+/// Installed into every new workspace.
+pub(crate) const DEFAULT_THEME: &str = "default";
+
+// SHA-256 of each layout the starter template shipped before themes existed.
+// Workspaces created then have no theme state, and these count as unmodified.
+const SHIPPED_LAYOUT_HASHES: [(&str, &str); 4] = [
+    (
+        "src/layouts/Layout.astro",
+        "2120015aaa46c9f07f6e86145b2cea89c081870f44d0724bdf12553dd98e4cba",
+    ),
+    (
+        "src/layouts/Layout.astro",
+        "938af011069e0ad79ecc89f2010b8a8fe41651bd2540a6fcbf064379a9090202",
+    ),
+    (
+        "src/layouts/PostLayout.astro",
+        "683d77a1053d03dce8779a6ef28079838578fa1a357758d3dcd0606360467ee6",
+    ),
+    (
+        "src/layouts/ProjectLayout.astro",
+        "b99357a59e2b2d9c5c91859f5417bd395587bf0becc844811c3dc8c76a0fe10b",
+    ),
+];
+
+#[derive(Debug, Deserialize)]
+struct ThemeManifest {
+    name: String,
+    #[serde(default)]
+    description: String,
+    version: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ThemeInfo {
+    id: String,
+    name: String,
+    description: String,
+    version: String,
+    files: Vec<String>, // Workspace-relative paths the theme provides
+    active: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct InstalledTheme {
+    #[serde(default)]
+    theme: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    files: BTreeMap<String, String>, // Path -> SHA-256 of the content we wrote
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeFileAction {
+    Create,
+    Update,
+    Unchanged,
+    Conflict, // Changed since the theme installed it; kept unless forced
+}
+
+#[derive(Debug, Serialize)]
+pub struct ThemeFileChange {
+    path: String,
+    action: ThemeFileAction,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ThemePreview {
+    theme: ThemeInfo,
+    changes: Vec<ThemeFileChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ThemeApplyResult {
+    theme: String,
+    written: Vec<String>,
+    conflicts: Vec<String>, // Kept as they are; pass `force` to replace them
+}
+
+struct Theme {
+    info: ThemeInfo,
+    dir: PathBuf,
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+pub(crate) fn themes_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .resolve("resources/themes", tauri::path::BaseDirectory::Resource)
+        .map_err(|e| format!("Failed to resolve themes path: {}", e))
+}
+
+fn collect_files(dir: &Path, prefix: &str, files: &mut Vec<String>) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let relative = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}/{}", prefix, name)
+        };
+
+        if entry.path().is_dir() {
+            collect_files(&entry.path(), &relative, files)?;
+        } else if relative != THEME_MANIFEST {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
+
+fn load_installed(studio_path: &Path) -> Result<InstalledTheme, String> {
+    let path = studio_path.join(".studio").join(THEME_STATE_FILE);
+    if !path.exists() {
+        return Ok(InstalledTheme::default());
+    }
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read theme state: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse theme state: {}", e))
+}
+
+fn save_installed(studio_path: &Path, installed: &InstalledTheme) -> Result<(), String> {
+    let dir = studio_path.join(".studio");
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create .studio directory: {}", e))?;
+    let content = serde_json::to_string_pretty(installed)
+        .map_err(|e| format!("Failed to serialize theme state: {}", e))?;
+    crate::write_file_atomic(&dir.join(THEME_STATE_FILE), &content)
+}
+
+fn load_theme(themes_path: &Path, id: &str, active: Option<&str>) -> Result<Theme, String> {
+    let valid_id = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    let dir = themes_path.join(id);
+    if !valid_id || !dir.join(THEME_MANIFEST).is_file() {
+        return Err(format!("Theme '{}' does not exist", id));
+    }
+
+    let content = fs::read_to_string(dir.join(THEME_MANIFEST))
+        .map_err(|e| format!("Failed to read theme '{}': {}", id, e))?;
+    let manifest: ThemeManifest = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse theme '{}': {}", id, e))?;
+
+    let mut files = Vec::new();
+    collect_files(&dir, "", &mut files)?;
+    files.sort();
+
+    Ok(Theme {
+        info: ThemeInfo {
+            id: id.to_string(),
+            name: manifest.name,
+            description: manifest.description,
+            version: manifest.version,
+            files,
+            active: active == Some(id),
+        },
+        dir,
+    })
+}
+
+/// What applying `theme` would do to each of its files. A file the workspace
+/// already has may only be replaced when it still matches the hash recorded
+/// when a theme installed it, or for workspaces from before themes, a layout
+/// the starter template shipped; otherwise the author changed it.
+fn plan_theme(
+    studio_path: &Path,
+    theme: &Theme,
+    installed: &InstalledTheme,
+) -> Result<Vec<ThemeFileChange>, String> {
+    let mut changes = Vec::new();
+
+    for relative in &theme.info.files {
+        let incoming = fs::read(theme.dir.join(relative))
+            .map_err(|e| format!("Failed to read theme file {}: {}", relative, e))?;
+        let action = match fs::read(studio_path.join(relative)) {
+            Err(_) => ThemeFileAction::Create,
+            Ok(current) if current == incoming => ThemeFileAction::Unchanged,
+            Ok(current) => {
+                let hash = sha256_hex(&current);
+                let pristine = match installed.theme {
+                    Some(_) => installed.files.get(relative) == Some(&hash),
+                    None => SHIPPED_LAYOUT_HASHES
+                        .iter()
+                        .any(|(path, shipped)| path == relative && *shipped == hash),
+                };
+                if pristine {
+                    ThemeFileAction::Update
+                } else {
+                    ThemeFileAction::Conflict
+                }
+            }
+        };
+        changes.push(ThemeFileChange {
+            path: relative.clone(),
+            action,
+        });
+    }
+
+    Ok(changes)
+}
+
+/// Copies a theme's files into the workspace and records their hashes.
+pub(crate) fn install_theme(
+    themes_path: &Path,
+    studio_path: &Path,
+    id: &str,
+    force: bool,
+) -> Result<ThemeApplyResult, String> {
+    let mut installed = load_installed(studio_path)?;
+    let theme = load_theme(themes_path, id, installed.theme.as_deref())?;
+    let changes = plan_theme(studio_path, &theme, &installed)?;
+
+    let mut written = Vec::new();
+    let mut conflicts = Vec::new();
+    for change in changes {
+        let replace = match change.action {
+            ThemeFileAction::Create | ThemeFileAction::Update => true,
+            ThemeFileAction::Conflict => force,
+            ThemeFileAction::Unchanged => false,
+        };
+        let content = fs::read_to_string(theme.dir.join(&change.path))
+            .map_err(|e| format!("Failed to read theme file {}: {}", change.path, e))?;
+
+        if replace {
+            let target = studio_path.join(&change.path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            crate::write_file_atomic(&target, &content)?;
+            written.push(change.path.clone());
+        } else if change.action == ThemeFileAction::Conflict {
+            // Keep the old hash so the file still counts as modified later
+            conflicts.push(change.path);
+            continue;
+        }
+        installed
+            .files
+            .insert(change.path, sha256_hex(content.as_bytes()));
+    }
+
+    installed.theme = Some(theme.info.id.clone());
+    installed.version = Some(theme.info.version.clone());
+    save_installed(studio_path, &installed)?;
+
+    Ok(ThemeApplyResult {
+        theme: theme.info.id,
+        written,
+        conflicts,
+    })
+}
+
+#[tauri::command]
+pub async fn list_themes(app: AppHandle) -> Result<Vec<ThemeInfo>, String> {
+    let themes_path = themes_dir(&app)?;
+    let active = load_installed(&crate::studio_dir()?)?.theme;
+
+    let entries = fs::read_dir(&themes_path)
+        .map_err(|e| format!("Failed to read themes directory: {}", e))?;
+    let mut themes = Vec::new();
+    for entry in entries.flatten() {
+        if !entry.path().join(THEME_MANIFEST).is_file() {
+            continue;
+        }
+        let id = entry.file_name().to_string_lossy().to_string();
+        themes.push(load_theme(&themes_path, &id, active.as_deref())?.info);
+    }
+
+    themes.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(themes)
+}
+
+/// Shows which files applying the theme would create, update or leave alone,
+/// and which the author has changed.
+#[tauri::command]
+pub async fn preview_theme(app: AppHandle, theme_id: String) -> Result<ThemePreview, String> {
+    let studio_path = crate::studio_dir()?;
+    let installed = load_installed(&studio_path)?;
+    let theme = load_theme(&themes_dir(&app)?, &theme_id, installed.theme.as_deref())?;
+    let changes = plan_theme(&studio_path, &theme, &installed)?;
+
+    Ok(ThemePreview {
+        theme: theme.info,
+        changes,
+    })
+}
+
+/// Switches the workspace to a theme. Files changed since a theme installed
+/// them are kept and reported as conflicts unless `force` is set.
+#[tauri::command]
+pub async fn apply_theme(
+    app: AppHandle,
+    theme_id: String,
+    force: Option<bool>,
+) -> Result<ThemeApplyResult, String> {
+    install_theme(
+        &themes_dir(&app)?,
+        &crate::studio_dir()?,
+        &theme_id,
+        force.unwrap_or(false),
+    )
+}
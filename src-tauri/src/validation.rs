@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use unicode_normalization::UnicodeNormalization;
+
+// Top-level routes the site, Astro or the generated files already use
+const RESERVED_PROJECT_NAMES: [(&str, &str); 12] = [
+    ("index", "the site's home page"),
+    ("api", "server endpoints"),
+    ("_astro", "Astro's built assets"),
+    ("404", "the not-found page"),
+    ("500", "the error page"),
+    ("rss.xml", "the site's RSS feed"),
+    ("atom.xml", "the site's Atom feed"),
+    ("feed.json", "the site's JSON feed"),
+    ("sitemap.xml", "the sitemap"),
+    ("robots.txt", "robots.txt"),
+    (crate::taxonomy::TAG_PAGES_DIR, "the tag pages"),
+    ("media", "uploaded images and files"),
+];
+
+// Names a post or section can't take inside its project folder
+const RESERVED_POST_SLUGS: [(&str, &str); 1] = [("index", "the project's index page")];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameKind {
+    Project,
+    Section, // A section folder inside a project
+    Post,    // A post title, slugged with the workspace's slug settings
+    Slug,    // A post slug typed as such, e.g. when renaming
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameProblem {
+    Empty,         // Nothing usable is left after sanitization
+    Reserved,      // Clashes with a route the site or Astro uses
+    Hidden,        // Starts with `_` or `.`, which Astro leaves out of the site
+    Exists,        // Already taken in the same folder
+    CaseCollision, // Differs only in case, which clashes on case-insensitive file systems
+}
+
+#[derive(Debug, Serialize)]
+pub struct NameValidation {
+    name: String, // The input in Unicode NFC, as it would be stored
+    slug: String, // The folder name or post slug it produces
+    problem: Option<NameProblem>,
+    message: Option<String>,
+}
+
+impl NameValidation {
+    fn problem(mut self, problem: NameProblem, message: String) -> Self {
+        self.problem = Some(problem);
+        self.message = Some(message);
+        self
+    }
+
+    /// The normalized name and slug, or the message for the first problem found.
+    pub(crate) fn into_result(self) -> Result<(String, String), String> {
+        match self.message {
+            Some(message) => Err(message),
+            None => Ok((self.name, self.slug)),
+        }
+    }
+
+    /// Drops a collision for callers that number the slug instead, as `create_post` does.
+    pub(crate) fn allow_collision(mut self) -> Self {
+        if matches!(
+            self.problem,
+            Some(NameProblem::Exists | NameProblem::CaseCollision)
+        ) {
+            self.problem = None;
+            self.message = None;
+        }
+        self
+    }
+}
+
+/// How names compare on disk: NFC, so macOS's decomposed names match, and case-folded.
+fn fold(name: &str) -> String {
+    name.nfc().collect::<String>().to_lowercase()
+}
+
+pub(crate) fn normalize(name: &str) -> String {
+    name.trim().nfc().collect()
+}
+
+/// The name in `dir` that `slug` would collide with, if any. Files count by
+/// their name without extension, since that is the route they are served at.
+/// `current` is the entry being renamed, which may keep its own name.
+pub(crate) fn find_collision(dir: &Path, slug: &str, current: Option<&str>) -> Option<String> {
+    let wanted = fold(slug);
+    let current = current.map(fold);
+
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            if name.starts_with('.') || name.starts_with('_') {
+                return None;
+            }
+            if entry.path().is_dir() {
+                return Some(name);
+            }
+            let stem = name
+                .rsplit_once('.')
+                .map_or(name.as_str(), |(stem, _)| stem);
+            Some(stem.to_string())
+        })
+        .find(|existing| fold(existing) == wanted && Some(fold(existing)) != current)
+}
+
+/// Checks a new project name or post title against the folder it would go in.
+pub(crate) fn validate(
+    kind: NameKind,
+    dir: &Path,
+    name: &str,
+    current: Option<&str>,
+) -> NameValidation {
+    let name = normalize(name);
+    let (slug, label, reserved): (String, &str, &[(&str, &str)]) = match kind {
+        NameKind::Project => (
+            crate::sanitize_project_name(&name),
+            "project name",
+            &RESERVED_PROJECT_NAMES,
+        ),
+        NameKind::Section => (
+            crate::sanitize_project_name(&name),
+            "section name",
+            &RESERVED_POST_SLUGS,
+        ),
+        NameKind::Post => (
+            crate::slug::title_slug(&name, &crate::slug::workspace_settings()),
+            "post slug",
+            &RESERVED_POST_SLUGS,
+        ),
+        NameKind::Slug => (
+            crate::slug::explicit_slug(&name, &crate::slug::workspace_settings()),
+            "post slug",
+            &RESERVED_POST_SLUGS,
+        ),
+    };
+    let validation = NameValidation {
+        name,
+        slug: slug.clone(),
+        problem: None,
+        message: None,
+    };
+
+    if slug.is_empty() {
+        let message = match kind {
+            NameKind::Project => "Project name cannot be empty after sanitization",
+            NameKind::Section => "Section name cannot be empty after sanitization",
+            NameKind::Post => "Post title cannot be empty after sanitization",
+            NameKind::Slug => "Post slug cannot be empty after sanitization",
+        };
+        return validation.problem(NameProblem::Empty, message.to_string());
+    }
+
+    let folded = fold(&slug);
+    if let Some((_, reason)) = reserved.iter().find(|(reserved, _)| *reserved == folded) {
+        return validation.problem(
+            NameProblem::Reserved,
+            format!(
+                "'{}' can't be used as a {}: it is reserved for {}",
+                slug, label, reason
+            ),
+        );
+    }
+    if slug.starts_with('_') || slug.starts_with('.') {
+        return validation.problem(
+            NameProblem::Hidden,
+            format!(
+                "'{}' can't be used as a {}: Astro leaves names starting with '{}' out of the site",
+                slug,
+                label,
+                &slug[..1]
+            ),
+        );
+    }
+
+    match find_collision(dir, &slug, current) {
+        Some(existing) if existing == slug => {
+            let existing_path = dir.join(&slug);
+            let message = match kind {
+                NameKind::Project if !existing_path.is_dir() => {
+                    format!("A page '{}' already exists in src/pages", slug)
+                }
+                NameKind::Project if !crate::adopt::is_managed(&existing_path) => format!(
+                    "Folder '{}' already exists; adopt it to make it a project",
+                    slug
+                ),
+                NameKind::Project => format!("Project '{}' already exists", slug),
+                NameKind::Section | NameKind::Post | NameKind::Slug => {
+                    format!("A post or section '{}' already exists", slug)
+                }
+            };
+            validation.problem(NameProblem::Exists, message)
+        }
+        Some(existing) => validation.problem(
+            NameProblem::CaseCollision,
+            format!(
+                "'{}' differs only in case from the existing '{}', which clashes on case-insensitive file systems",
+                slug, existing
+            ),
+        ),
+        None => validation,
+    }
+}
+
+/// Checks a name as the user types it. Sections and posts are checked
+/// against `project_name`, which may itself be a section path; pass the folder or slug being renamed as `current`.
+/// `create_post` numbers a colliding slug instead of failing, so for posts
+/// `exists` and `case_collision` are only errors when renaming.
+#[tauri::command]
+pub async fn validate_name(
+    kind: NameKind,
+    name: String,
+    project_name: Option<String>,
+    current: Option<String>,
+) -> Result<NameValidation, String> {
+    let studio_path = crate::studio_dir()?;
+    let dir = match kind {
+        NameKind::Project => studio_path.join("src").join("pages"),
+        NameKind::Section | NameKind::Post | NameKind::Slug => {
+            let project_name = project_name.ok_or("Checking a post name needs its project")?;
+            crate::sections::resolve_project_path(&studio_path, &project_name)?
+        }
+    };
+
+    Ok(validate(kind, &dir, &name, current.as_deref()))
+}
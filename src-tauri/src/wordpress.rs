@@ -0,0 +1,534 @@
+use crate::frontmatter;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path};
+
+const UPLOADS_MARKER: &str = "wp-content/uploads/";
+
+#[derive(Debug, Default)]
+struct WxrItem {
+    title: String,
+    post_name: String,
+    post_date: String,
+    post_date_gmt: String,
+    post_type: String,
+    status: String,
+    content: String,
+    excerpt: String,
+    categories: Vec<String>,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportedPost {
+    title: String,
+    slug: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportFailure {
+    item: String, // Post title, or the media URL that could not be copied
+    reason: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    imported: Vec<ImportedPost>,
+    failures: Vec<ImportFailure>,
+    skipped: usize, // Pages, attachments and other non-post items
+}
+
+fn parse_wxr(xml: &str) -> Result<Vec<WxrItem>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut items = Vec::new();
+    let mut item: Option<WxrItem> = None;
+    let mut text = String::new();
+    let mut category_domain = String::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                if name == "item" {
+                    item = Some(WxrItem::default());
+                } else if name == "category" {
+                    category_domain = e
+                        .try_get_attribute("domain")
+                        .ok()
+                        .flatten()
+                        .map(|attr| String::from_utf8_lossy(&attr.value).to_string())
+                        .unwrap_or_default();
+                }
+                text.clear();
+            }
+            Ok(Event::Text(e)) => {
+                let unescaped = e
+                    .unescape()
+                    .map_err(|e| format!("Invalid text in export file: {}", e))?;
+                text.push_str(&unescaped);
+            }
+            Ok(Event::CData(e)) => text.push_str(&String::from_utf8_lossy(&e.into_inner())),
+            Ok(Event::End(e)) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                let value = std::mem::take(&mut text);
+
+                if name == "item" {
+                    items.extend(item.take());
+                    continue;
+                }
+
+                let Some(current) = item.as_mut() else {
+                    continue;
+                };
+                match name.as_str() {
+                    "title" => current.title = value.trim().to_string(),
+                    "wp:post_name" => current.post_name = value.trim().to_string(),
+                    "wp:post_date" => current.post_date = value.trim().to_string(),
+                    "wp:post_date_gmt" => current.post_date_gmt = value.trim().to_string(),
+                    "wp:post_type" => current.post_type = value.trim().to_string(),
+                    "wp:status" => current.status = value.trim().to_string(),
+                    "content:encoded" => current.content = value,
+                    "excerpt:encoded" => current.excerpt = value.trim().to_string(),
+                    "category" if category_domain == "category" => {
+                        current.categories.push(value.trim().to_string())
+                    }
+                    "category" if category_domain == "post_tag" => {
+                        current.tags.push(value.trim().to_string())
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => {
+                return Err(format!(
+                    "Failed to parse export file at byte {}: {}",
+                    reader.error_position(),
+                    e
+                ))
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+/// WordPress stores classic-editor posts without `<p>` tags and adds them when
+/// rendering; do the same so paragraphs survive the markdown conversion.
+fn auto_paragraphs(html: &str) -> String {
+    if html.contains("<p") {
+        return html.to_string();
+    }
+
+    html.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .map(|block| format!("<p>{}</p>", block.replace('\n', "<br />")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn html_to_markdown(html: &str) -> String {
+    html2md::parse_html(&auto_paragraphs(html))
+        .trim()
+        .to_string()
+}
+
+/// Copies every referenced upload that exists in the local uploads folder into
+/// the post's assets folder and points the markdown at the copies. Uploads keep
+/// their `year/month` path, so files with the same name don't overwrite each other.
+fn import_media(
+    markdown: &str,
+    uploads_dir: Option<&Path>,
+    assets_dir: &Path,
+    assets_url: &str,
+    failures: &mut Vec<ImportFailure>,
+) -> String {
+    let mut result = markdown.to_string();
+    let mut urls: Vec<String> = Vec::new();
+
+    for (index, _) in markdown.match_indices(UPLOADS_MARKER) {
+        let start = markdown[..index]
+            .rfind(|c: char| c == '(' || c == '"' || c == '\'' || c.is_whitespace())
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let end = markdown[index..]
+            .find(|c: char| c == ')' || c == '"' || c == '\'' || c.is_whitespace())
+            .map(|i| index + i)
+            .unwrap_or(markdown.len());
+        let url = &markdown[start..end];
+        if !urls.iter().any(|seen| seen == url) {
+            urls.push(url.to_string());
+        }
+    }
+
+    for url in urls {
+        let relative = url[url.find(UPLOADS_MARKER).unwrap_or(0) + UPLOADS_MARKER.len()..]
+            .split(['?', '#'])
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let safe = Path::new(&relative)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        if relative.is_empty() || relative.ends_with('/') || !safe {
+            continue;
+        }
+
+        let Some(source) = uploads_dir
+            .map(|dir| dir.join(&relative))
+            .filter(|path| path.is_file())
+        else {
+            failures.push(ImportFailure {
+                item: url.clone(),
+                reason: "Not found in the uploads folder".to_string(),
+            });
+            continue;
+        };
+
+        let target = assets_dir.join(&relative);
+        let copied = fs::create_dir_all(target.parent().unwrap_or(assets_dir))
+            .and_then(|_| fs::copy(&source, &target));
+        match copied {
+            Ok(_) => result = result.replace(&url, &format!("{}/{}", assets_url, relative)),
+            Err(e) => failures.push(ImportFailure {
+                item: url.clone(),
+                reason: format!("Failed to copy: {}", e),
+            }),
+        }
+    }
+
+    result
+}
+
+/// Builds the frontmatter for an imported post, mapping WordPress statuses onto ours.
+fn imported_frontmatter(item: &WxrItem, title: &str) -> String {
+    let date = item
+        .post_date
+        .get(..10)
+        .filter(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok())
+        .map(|date| date.to_string())
+        .unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%d").to_string());
+
+    let mut lines = vec![
+        format!("title: {}", frontmatter::quote(title)),
+        format!("date: {}", date),
+    ];
+
+    match item.status.as_str() {
+        "publish" => lines.push("status: published".to_string()),
+        "pending" => lines.push("status: in_review".to_string()),
+        "future" => {
+            lines.push("status: scheduled".to_string());
+            let publish_at =
+                chrono::NaiveDateTime::parse_from_str(&item.post_date_gmt, "%Y-%m-%d %H:%M:%S")
+                    .map(|at| at.and_utc().to_rfc3339())
+                    .unwrap_or_else(|_| date.clone());
+            lines.push(format!("publishAt: {}", frontmatter::quote(&publish_at)));
+        }
+        _ => lines.push("status: draft".to_string()),
+    }
+
+    if !item.excerpt.is_empty() {
+        // Descriptions are a single line, however the excerpt was wrapped
+        let description = html_to_markdown(&item.excerpt)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        lines.push(format!("description: {}", frontmatter::quote(&description)));
+    }
+    if !item.categories.is_empty() {
+        lines.push(format!(
+            "categories: {}",
+            frontmatter::format_list(&item.categories)
+        ));
+    }
+    if !item.tags.is_empty() {
+        lines.push(format!("tags: {}", frontmatter::format_list(&item.tags)));
+    }
+
+    lines.join("\n")
+}
+
+/// WordPress stores slugs with letters outside ASCII percent-encoded, e.g. `caf%c3%a9`.
+fn decode_post_name(post_name: &str) -> String {
+    let bytes = post_name.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = post_name
+            .get(index + 1..index + 3)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) if bytes[index] == b'%' => {
+                decoded.push(byte);
+                index += 3;
+            }
+            _ => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| post_name.to_string())
+}
+
+fn import_item(
+    studio_path: &Path,
+    project_name: &str,
+    item: &WxrItem,
+    uploads_dir: Option<&Path>,
+    failures: &mut Vec<ImportFailure>,
+) -> Result<ImportedPost, String> {
+    let project_path = studio_path.join("src").join("pages").join(project_name);
+    let title = if item.title.is_empty() {
+        "Untitled".to_string()
+    } else {
+        item.title.clone()
+    };
+
+    // The post's own slug is kept as written unless it can't be used here,
+    // e.g. `index`; then one comes from the title
+    let from_title = || {
+        crate::validation::validate(
+            crate::validation::NameKind::Post,
+            &project_path,
+            &title,
+            None,
+        )
+        .allow_collision()
+        .into_result()
+    };
+    let (_, base_slug) = if item.post_name.is_empty() {
+        from_title()?
+    } else {
+        crate::validation::validate(
+            crate::validation::NameKind::Slug,
+            &project_path,
+            &decode_post_name(&item.post_name),
+            None,
+        )
+        .allow_collision()
+        .into_result()
+        .or_else(|_| from_title())?
+    };
+    let slug = crate::unique_post_slug(&project_path, &base_slug, None);
+
+    let markdown = import_media(
+        &html_to_markdown(&item.content),
+        uploads_dir,
+        &crate::post_assets_dir(studio_path, project_name, &slug),
+        &format!("/media/{}/{}", project_name, slug),
+        failures,
+    );
+    let content = format!(
+        "---\n{}\n---\n\n{}\n",
+        imported_frontmatter(item, &title),
+        markdown
+    );
+
+    crate::write_file_atomic(&project_path.join(format!("{}.md", slug)), &content)?;
+
+    Ok(ImportedPost { title, slug })
+}
+
+#[tauri::command]
+pub async fn import_wordpress(
+    project_name: String,
+    wxr_path: String,
+    uploads_dir: Option<String>,
+) -> Result<ImportReport, String> {
+    let studio_path = crate::studio_dir()?;
+    crate::sections::resolve_project_path(&studio_path, &project_name)?;
+
+    let xml =
+        fs::read_to_string(&wxr_path).map_err(|e| format!("Failed to read export file: {}", e))?;
+    let items = parse_wxr(&xml)?;
+    let uploads_dir = uploads_dir.map(std::path::PathBuf::from);
+
+    let mut report = ImportReport::default();
+    for item in &items {
+        if item.post_type != "post" {
+            report.skipped += 1;
+            continue;
+        }
+
+        match import_item(
+            &studio_path,
+            &project_name,
+            item,
+            uploads_dir.as_deref(),
+            &mut report.failures,
+        ) {
+            Ok(imported) => report.imported.push(imported),
+            Err(reason) => report.failures.push(ImportFailure {
+                item: item.title.clone(),
+                reason,
+            }),
+        }
+    }
+
+    crate::refresh_site_files(&studio_path)?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WXR: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0"
+    xmlns:excerpt="http://wordpress.org/export/1.2/excerpt/"
+    xmlns:content="http://purl.org/rss/1.0/modules/content/"
+    xmlns:wp="http://wordpress.org/export/1.2/">
+<channel>
+    <title>A Blog</title>
+    <item>
+        <title>Café &amp; Bar</title>
+        <wp:post_name><![CDATA[caf%c3%a9-bar]]></wp:post_name>
+        <wp:post_date><![CDATA[2024-03-01 09:30:00]]></wp:post_date>
+        <wp:post_date_gmt><![CDATA[2024-03-01 08:30:00]]></wp:post_date_gmt>
+        <wp:post_type><![CDATA[post]]></wp:post_type>
+        <wp:status><![CDATA[publish]]></wp:status>
+        <content:encoded><![CDATA[First line
+second line
+
+<img src="https://blog.example/wp-content/uploads/2024/03/photo.jpg" />]]></content:encoded>
+        <excerpt:encoded><![CDATA[A short
+excerpt]]></excerpt:encoded>
+        <category domain="category" nicename="travel"><![CDATA[Travel]]></category>
+        <category domain="post_tag" nicename="coffee"><![CDATA[coffee]]></category>
+    </item>
+    <item>
+        <title>Coming Soon</title>
+        <wp:post_name><![CDATA[coming-soon]]></wp:post_name>
+        <wp:post_date><![CDATA[2030-01-02 10:00:00]]></wp:post_date>
+        <wp:post_date_gmt><![CDATA[2030-01-02 09:00:00]]></wp:post_date_gmt>
+        <wp:post_type><![CDATA[post]]></wp:post_type>
+        <wp:status><![CDATA[future]]></wp:status>
+        <content:encoded><![CDATA[<p>Soon.</p>]]></content:encoded>
+        <excerpt:encoded><![CDATA[]]></excerpt:encoded>
+    </item>
+    <item>
+        <title>Waiting</title>
+        <wp:post_name><![CDATA[waiting]]></wp:post_name>
+        <wp:post_date><![CDATA[2024-04-01 10:00:00]]></wp:post_date>
+        <wp:post_type><![CDATA[post]]></wp:post_type>
+        <wp:status><![CDATA[pending]]></wp:status>
+        <content:encoded><![CDATA[]]></content:encoded>
+    </item>
+    <item>
+        <title>About</title>
+        <wp:post_name><![CDATA[about]]></wp:post_name>
+        <wp:post_type><![CDATA[page]]></wp:post_type>
+        <wp:status><![CDATA[publish]]></wp:status>
+    </item>
+    <item>
+        <title>photo</title>
+        <wp:post_name><![CDATA[photo]]></wp:post_name>
+        <wp:post_type><![CDATA[attachment]]></wp:post_type>
+        <wp:status><![CDATA[inherit]]></wp:status>
+    </item>
+</channel>
+</rss>"#;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("studio-wordpress-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parses_posts_pages_and_attachments() {
+        let items = parse_wxr(WXR).unwrap();
+        let types: Vec<_> = items.iter().map(|item| item.post_type.as_str()).collect();
+        assert_eq!(types, ["post", "post", "post", "page", "attachment"]);
+
+        let post = &items[0];
+        assert_eq!(post.title, "Café & Bar");
+        assert_eq!(decode_post_name(&post.post_name), "café-bar");
+        assert_eq!(post.categories, ["Travel"]);
+        assert_eq!(post.tags, ["coffee"]);
+        assert!(post.content.starts_with("First line\nsecond line"));
+    }
+
+    #[test]
+    fn decodes_only_valid_escapes() {
+        assert_eq!(decode_post_name("caf%c3%a9"), "café");
+        assert_eq!(decode_post_name("100%-done"), "100%-done");
+        assert_eq!(decode_post_name("bad%ff"), "bad%ff");
+    }
+
+    #[test]
+    fn adds_paragraphs_to_classic_posts_only() {
+        assert_eq!(
+            auto_paragraphs("First line\nsecond line\n\nNext"),
+            "<p>First line<br />second line</p>\n<p>Next</p>"
+        );
+        assert_eq!(auto_paragraphs("<p>Kept</p>"), "<p>Kept</p>");
+    }
+
+    #[test]
+    fn maps_statuses_onto_ours() {
+        let items = parse_wxr(WXR).unwrap();
+
+        let published = imported_frontmatter(&items[0], &items[0].title);
+        assert!(published.contains("date: 2024-03-01"));
+        assert!(published.contains("status: published"));
+        assert!(published.contains("description: \"A short excerpt\""));
+        assert!(published.contains("categories: "));
+        assert!(published.contains("tags: "));
+
+        let scheduled = imported_frontmatter(&items[1], &items[1].title);
+        assert!(scheduled.contains("status: scheduled"));
+        assert!(scheduled.contains("publishAt: \"2030-01-02T09:00:00+00:00\""));
+
+        let pending = imported_frontmatter(&items[2], &items[2].title);
+        assert!(pending.contains("status: in_review"));
+        assert!(!pending.contains("description:"));
+
+        let page = imported_frontmatter(&items[3], &items[3].title);
+        assert!(page.contains("status: published"));
+    }
+
+    #[test]
+    fn copies_uploads_and_rejects_paths_outside_them() {
+        let root = temp_dir("media");
+        let uploads_dir = root.join("uploads");
+        let assets_dir = root.join("assets");
+        fs::create_dir_all(uploads_dir.join("2024/03")).unwrap();
+        fs::write(uploads_dir.join("2024/03/photo.jpg"), "photo").unwrap();
+        fs::write(root.join("secret.txt"), "secret").unwrap();
+
+        let markdown = "![](https://blog.example/wp-content/uploads/2024/03/photo.jpg)\n\
+            ![](https://blog.example/wp-content/uploads/../secret.txt)\n\
+            ![](https://blog.example/wp-content/uploads/2024/03/missing.jpg)";
+        let mut failures = Vec::new();
+        let result = import_media(
+            markdown,
+            Some(&uploads_dir),
+            &assets_dir,
+            "/media/blog/post",
+            &mut failures,
+        );
+
+        assert!(result.contains("![](/media/blog/post/2024/03/photo.jpg)"));
+        assert_eq!(
+            fs::read_to_string(assets_dir.join("2024/03/photo.jpg")).unwrap(),
+            "photo"
+        );
+
+        assert!(result.contains("wp-content/uploads/../secret.txt"));
+        assert!(!assets_dir.join("secret.txt").exists());
+
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].item.ends_with("2024/03/missing.jpg"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,8 +1,15 @@
+export type PostStatus = 'draft' | 'in_review' | 'scheduled' | 'published';
+
+export type PostFormat = 'md' | 'mdx';
+
 export interface Post {
   filename: string;
   title: string;
   slug: string;
   content: string;
+  status: PostStatus;
+  publish_at?: string | null; // RFC 3339, only set for scheduled posts
+  format: PostFormat;
 }
 
 export interface Project {
@@ -18,4 +25,159 @@ export interface Project {
   name: string; // Display name (matches Rust backend)
   folder_name: string; // Actual folder name (sanitized)
   path: string;
+  managed?: boolean; // False for folders without studio scaffolding; see adopt_project
+}
+
+export type FeedContent = 'full' | 'summary';
+
+export interface ProjectMetadata {
+  description?: string;
+  author?: string;
+  language?: string; // BCP 47 tag, e.g. "en" or "pt-BR"
+  cover?: string; // Site path of the cover image, e.g. /media/essays/cover.jpg
+  feed_content?: FeedContent; // Defaults to summary
+}
+
+export type Taxonomy = 'tags' | 'categories';
+
+export interface TagCount {
+  name: string;
+  count: number;
+}
+
+export interface TaggedPost {
+  project: string;
+  slug: string;
+  title: string;
+  status: PostStatus;
+}
+
+export interface ProjectTreeNode {
+  name: string;
+  path: string;
+  url: string;
+  posts: Post[];
+  sections: ProjectTreeNode[];
+}
+
+export interface Section {
+  name: string;
+  path: string;
+  url: string;
+}
+
+export interface ThemeColors {
+  primary?: string;
+  background?: string;
+  text?: string;
+  muted?: string;
+}
+
+export interface NavLink {
+  label: string;
+  href: string;
+}
+
+export type SlugStrategy = 'unicode' | 'ascii' | 'romanize';
+
+export interface SlugSettings {
+  strategy?: SlugStrategy; // Defaults to unicode
+  max_length?: number; // Characters, at least 10; cut at a word boundary
+  remove_stop_words?: boolean;
+}
+
+export interface SiteConfig {
+  title?: string;
+  description?: string;
+  url?: string;
+  language?: string;
+  colors?: ThemeColors;
+  nav?: NavLink[];
+  robots?: { disallow_all?: boolean; disallow?: string[] };
+  slugs?: SlugSettings;
+  project_order?: string[]; // Read-only here; set through reorder_projects
+  [key: string]: unknown; // Settings kept as they are
+}
+
+export interface ThemeInfo {
+  id: string;
+  name: string;
+  description: string;
+  version: string;
+  files: string[];
+  active: boolean;
+}
+
+export type ThemeFileAction = 'create' | 'update' | 'unchanged' | 'conflict';
+
+export interface ThemePreview {
+  theme: ThemeInfo;
+  changes: { path: string; action: ThemeFileAction }[];
+}
+
+export interface ThemeApplyResult {
+  theme: string;
+  written: string[];
+  conflicts: string[]; // Kept as they are; apply with force to replace them
+}
+
+export type TemplateFileStatus = 'create' | 'update' | 'merge' | 'conflict';
+
+export interface TemplateUpdateCheck {
+  installed_version: string | null; // null for workspaces created before versioning
+  bundled_version: string;
+  update_available: boolean;
+  files: { path: string; status: TemplateFileStatus }[];
+}
+
+export interface TemplateUpgradeResult {
+  version: string;
+  created: string[];
+  updated: string[];
+  merged: string[];
+  conflicts: { path: string; merged: string }[]; // merged holds conflict markers
+}
+
+export type CheckStatus = 'pass' | 'warn' | 'fail';
+
+export interface DoctorCheck {
+  id: string; // e.g. "node" or "project:docs/guides"
+  title: string;
+  status: CheckStatus;
+  message: string;
+  fix: string | null;
+}
+
+export type RepairAction = 'create' | 'update' | 'unchanged';
+
+export type NameSource = 'manifest' | 'index_page' | 'folder_name';
+
+export interface RepairFile {
+  file: string;
+  action: RepairAction;
+  diff: string; // Unified diff; empty when unchanged
+}
+
+export interface RepairPlan {
+  folder_name: string;
+  display_name: string;
+  name_source: NameSource;
+  files: RepairFile[];
+}
+
+export interface AdoptResult {
+  project: Pick<Project, 'name' | 'folder_name' | 'path' | 'managed'>;
+  created: string[]; // Paths below the project folder, e.g. "guides/index.astro"
+  kept: string[];
+}
+
+export type NameKind = 'project' | 'section' | 'post' | 'slug'; // post checks a title, slug a typed slug
+
+export type NameProblem = 'empty' | 'reserved' | 'hidden' | 'exists' | 'case_collision';
+
+export interface NameValidation {
+  name: string; // Unicode NFC, as it would be stored
+  slug: string; // The folder name or post slug it produces
+  problem: NameProblem | null;
+  message: string | null;
 }